@@ -1,7 +1,7 @@
 use proc_easy::EasyAttributes;
 use syn::spanned::Spanned;
 
-use crate::{merge_where_clauses, Borrow, Name, OnDrop, OnReplace, WhereClause};
+use crate::{merge_where_clauses, Borrow, CompareOnWrite, Name, OnDrop, OnReplace, WhereClause};
 
 proc_easy::easy_attributes! {
     @(edict)
@@ -10,6 +10,7 @@ proc_easy::easy_attributes! {
         borrow: Option<Borrow>,
         on_drop: Option<OnDrop>,
         on_replace: Option<OnReplace>,
+        compare_on_write: Option<CompareOnWrite>,
         where_clauses: Vec<WhereClause>,
     }
 }
@@ -58,6 +59,15 @@ pub fn derive(
         }
     );
 
+    let value_compare = attributes.compare_on_write.map(|_| {
+        quote::quote! {
+            #[inline(always)]
+            fn value_compare() -> Option<#edict_path::component::ValueCompare> {
+                Some(#edict_path::component::ValueCompare::of::<Self>())
+            }
+        }
+    });
+
     let insert_borrows = match attributes.borrow {
         None => None,
         Some(borrow) => {
@@ -107,6 +117,8 @@ pub fn derive(
 
             #on_replace
 
+            #value_compare
+
             fn borrows() -> #edict_path::private::Vec<#edict_path::component::ComponentBorrow> {
                 let mut output = Vec::new();
                 output.push(#edict_path::component::ComponentBorrow::auto::<Self>());
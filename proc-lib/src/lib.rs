@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 
 mod component;
-// mod query;
+mod query;
 mod relation;
 mod system;
 
@@ -14,6 +14,7 @@ mod kw {
     proc_easy::easy_token!(exclusive);
     proc_easy::easy_token!(symmetric);
     proc_easy::easy_token!(owned);
+    proc_easy::easy_token!(compare_on_write);
 }
 
 proc_easy::easy_argument_value! {
@@ -66,6 +67,12 @@ proc_easy::easy_argument! {
     }
 }
 
+proc_easy::easy_argument! {
+    struct CompareOnWrite {
+        kw: kw::compare_on_write,
+    }
+}
+
 proc_easy::easy_argument! {
     struct WhereClause {
         kw: syn::Token![where],
@@ -133,16 +140,16 @@ pub fn derive_relation(
     }
 }
 
-// pub fn derive_query(
-//     item: TokenStream,
-//     edict_path: &syn::Path,
-//     edict_namespace: &syn::Ident,
-// ) -> TokenStream {
-//     match syn::parse2(item).and_then(|input| query::derive(input, edict_path, edict_namespace)) {
-//         Ok(output) => output.into(),
-//         Err(err) => err.to_compile_error().into(),
-//     }
-// }
+pub fn derive_query(
+    item: TokenStream,
+    edict_path: &syn::Path,
+    edict_namespace: &syn::Ident,
+) -> TokenStream {
+    match syn::parse2(item).and_then(|input| query::derive(input, edict_path, edict_namespace)) {
+        Ok(output) => output,
+        Err(err) => err.to_compile_error(),
+    }
+}
 
 pub fn system(item: syn::ItemFn, edict_path: &syn::Path) -> syn::Result<TokenStream> {
     system::system(item, edict_path)
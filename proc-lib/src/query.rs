@@ -1,188 +1,322 @@
-use syn::spanned::Spanned;
+use syn::{spanned::Spanned, visit_mut::VisitMut};
 
 pub fn derive(
     input: syn::DeriveInput,
     edict_path: &syn::Path,
     edict_namespace: &syn::Ident,
 ) -> syn::Result<proc_macro2::TokenStream> {
+    let _ = edict_namespace;
+
     let vis = &input.vis;
-    let ident: &proc_macro2::Ident = &input.ident;
+    let ident = &input.ident;
     let query_ident = quote::format_ident!("{}Query", ident);
+    let fetch_ident = quote::format_ident!("{}Fetch", ident);
 
-    match input.data {
-        syn::Data::Union(data) => Err(syn::Error::new_spanned(
-            data.union_token,
-            "Deriving `Query` is not supported for unions",
-        )),
-        syn::Data::Struct(data) => match data.fields {
-            syn::Fields::Unit => Err(syn::Error::new_spanned(
-                data.semi_token,
-                "Deriving `Query` is not supported for unit structs",
-            )),
-            syn::Fields::Named(fields) => {
-                let query_fields = fields.named.iter().map(|f| {
-                    let vis = &f.vis;
-                    let ident = f.ident.as_ref().unwrap();
-                    let ty = &f.ty;
-
-                    let query_ty =
-                        quote::quote_spanned! { ty.span() => <#ty as #edict_path::query::AsQuery>::Query };
-                    quote::quote_spanned! { f.span() => #vis #ident: #query_ty }
-                });
-                
-                let field_vis = fields.named.iter().map(|f| {
-                    &f.vis
-                }).collect::<Vec<_>>();
-
-                let field_names = fields.named.iter().map(|f| {
-                    f.ident.as_ref().unwrap()
-                }).collect::<Vec<_>>();
-
-                let field_types = fields.named.iter().map(|f| {
-                    &f.ty
-                }).collect::<Vec<_>>();
-
-                Ok(quote::quote! {
-                    struct #query_ident {
-                        #(#field_vis #query_fields,)*
-                    }
-                    
-                    impl #edict_path::query::AsQuery for #ident {
-                        type Query = #query_ident;
-                    }
-
-                    impl #edict_path::query::DefaultQuery for #ident {
-                        fn default_query() -> #query_ident {
-                            #query_ident{#(
-                                #field_names: <#field_types as #edict_path::query::DefaultQuery>::default_query(),
-                            )*}
-                        }
-                    }
-
-                    impl #edict_path::query::AsQuery for #query_ident {
-                        type Query = Self;
-                    }
-
-                    impl #edict_path::query::IntoQuery for #query_ident {
-                        fn into_query(self) -> Self {
-                            self
-                        }
-                    }
-
-                    impl #edict_path::query::DefaultQuery for #query_ident {
-                        fn default_query() -> #query_ident {
-                            #query_ident(#(
-                                <#field_types as #edict_path::query::DefaultQuery>::default_query(),
-                            )*)
-                        }
-                    }
-
-                    impl #edict_path::query::Query for #query_ident {
-                        type Item<'a> = 
-                    }
-                })
+    let lifetime = item_lifetime(&input.generics)?;
+
+    let data = match input.data {
+        syn::Data::Struct(data) => data,
+        syn::Data::Enum(data) => {
+            return Err(syn::Error::new_spanned(
+                data.enum_token,
+                "Deriving `Query` is not supported for enums",
+            ))
+        }
+        syn::Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "Deriving `Query` is not supported for unions",
+            ))
+        }
+    };
+
+    if data.fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Deriving `Query` requires at least one field",
+        ));
+    }
+
+    let members = data.fields.members().collect::<Vec<_>>();
+
+    let field_vis = data.fields.iter().map(|f| &f.vis).collect::<Vec<_>>();
+
+    // Query types for each field, with item lifetime replaced by `'static`.
+    let query_types = data
+        .fields
+        .iter()
+        .map(|f| {
+            let mut ty = f.ty.clone();
+            if let Some(lifetime) = lifetime {
+                ReplaceLifetime { lifetime }.visit_type_mut(&mut ty);
             }
-            syn::Fields::Unnamed(fields) => {
-                let query_fields = fields.unnamed.iter().map(|f| {
-                    let vis = &f.vis;
-                    let ty = &f.ty;
-
-                    quote::quote_spanned! { ty.span() => #vis <#ty as #edict_path::query::AsQuery>::Query }
-                });
-
-                let field_vis = fields.unnamed.iter().map(|f| {
-                    &f.vis
-                }).collect::<Vec<_>>();
-
-                let field_indices = (0..fields.unnamed.len()).collect::<Vec<_>>();
-
-                let field_types = fields.unnamed.iter().map(|f| {
-                    &f.ty
-                }).collect::<Vec<_>>();
-
-                Ok(quote::quote! {
-                    #vis struct #query_ident(
-                        #(#field_vis #query_fields,)*
-                    )
-
-                    impl #edict_path::query::AsQuery for #ident {
-                        type Query = #query_ident;
-                    }
-
-                    impl #edict_path::query::DefaultQuery for #ident {
-                        fn default_query() -> #query_ident {
-                            #query_ident(#(
-                                <#field_types as #edict_path::query::DefaultQuery>::default_query(),
-                            )*)
-                        }
-                    }
-
-                    impl #edict_path::query::AsQuery for #query_ident {
-                        type Query = Self;
-                    }
-
-                    impl #edict_path::query::IntoQuery for #query_ident {
-                        fn into_query(self) -> Self {
-                            self
-                        }
-                    }
-
-                    impl #edict_path::query::DefaultQuery for #query_ident {
-                        fn default_query() -> #query_ident {
-                            #query_ident(#(
-                                <#field_types as #edict_path::query::DefaultQuery>::default_query(),
-                            )*)
-                        }
-                    }
-                })
+            quote::quote_spanned! { f.ty.span() => <#ty as #edict_path::query::AsQuery>::Query }
+        })
+        .collect::<Vec<_>>();
+
+    let item_ty = match lifetime {
+        None => quote::quote! { #ident },
+        Some(_) => quote::quote! { #ident<'a> },
+    };
+
+    let as_query_generics = match lifetime {
+        None => quote::quote! {},
+        Some(lifetime) => quote::quote! { <#lifetime> },
+    };
+
+    let as_query_ty = match lifetime {
+        None => quote::quote! { #ident },
+        Some(lifetime) => quote::quote! { #ident<#lifetime> },
+    };
+
+    let (query_struct, fetch_struct) = match &data.fields {
+        syn::Fields::Named(_) => (
+            quote::quote! {
+                #vis struct #query_ident {
+                    #( #field_vis #members: #query_types, )*
+                }
+            },
+            quote::quote! {
+                #vis struct #fetch_ident<'a> {
+                    #( #members: <#query_types as #edict_path::query::Query>::Fetch<'a>, )*
+                }
+            },
+        ),
+        syn::Fields::Unnamed(_) => (
+            quote::quote! {
+                #vis struct #query_ident(
+                    #( #field_vis #query_types, )*
+                );
+            },
+            quote::quote! {
+                #vis struct #fetch_ident<'a>(
+                    #( <#query_types as #edict_path::query::Query>::Fetch<'a>, )*
+                );
+            },
+        ),
+        syn::Fields::Unit => unreachable!(),
+    };
+
+    let query_doc = format!("Query type for [`{ident}`].");
+    let fetch_doc = format!("Fetch type for [`{query_ident}`].");
+
+    Ok(quote::quote! {
+        #[doc = #query_doc]
+        #[derive(Clone, Copy)]
+        #query_struct
+
+        #[doc = #fetch_doc]
+        #fetch_struct
+
+        unsafe impl<'a> #edict_path::query::Fetch<'a> for #fetch_ident<'a> {
+            type Item = #item_ty;
+
+            #[inline(always)]
+            fn dangling() -> Self {
+                #fetch_ident {
+                    #( #members: #edict_path::query::Fetch::dangling(), )*
+                }
             }
-        },
-        syn::Data::Enum(data) => {
-            let query_variants = data.variants.iter().map(|v| {
-                match &v.fields {
-                    syn::Fields::Unit => Err(syn::Error::new_spanned(
-                        &v.ident,
-                        "Deriving `Query` is not supported for unit structs",
-                    )),
-                    syn::Fields::Named(fields) => {
-                        let query_fields = fields.named.iter().map(|f| {
-                            let vis = &f.vis;
-                            let ident = f.ident.as_ref().unwrap();
-                            let ty = &f.ty;
-        
-                            let query_ty =
-                                quote::quote_spanned! { ty.span() => <#ty as #edict_path::query::AsQuery>::Query };
-                            quote::quote_spanned! { f.span() => #vis #ident: #query_ty }
-                        });
-        
-                        Ok(quote::quote! {
-                            struct #query_ident {
-                                #(#query_fields,)*
-                            }
-                        })
-                    }
-                    syn::Fields::Unnamed(fields) => {
-                        let query_fields = fields.unnamed.iter().map(|f| {
-                            let vis = &f.vis;
-                            let ty = &f.ty;
-        
-                            quote::quote_spanned! { ty.span() => #vis <#ty as #edict_path::query::AsQuery>::Query }
-                        });
-        
-                        Ok(quote::quote! {
-                            struct #query_ident(
-                                #(#query_fields,)*
-                            )
-                        })
-                    }
+
+            #[inline(always)]
+            unsafe fn visit_chunk(&mut self, chunk_idx: u32) -> bool {
+                unsafe { true #( && #edict_path::query::Fetch::visit_chunk(&mut self.#members, chunk_idx) )* }
+            }
+
+            #[inline(always)]
+            unsafe fn visit_item(&mut self, idx: u32) -> bool {
+                unsafe { true #( && #edict_path::query::Fetch::visit_item(&mut self.#members, idx) )* }
+            }
+
+            #[inline(always)]
+            unsafe fn touch_chunk(&mut self, chunk_idx: u32) {
+                #( unsafe { #edict_path::query::Fetch::touch_chunk(&mut self.#members, chunk_idx) } )*
+            }
+
+            #[inline(always)]
+            unsafe fn get_item(&mut self, idx: u32) -> #item_ty {
+                #ident {
+                    #( #members: unsafe { #edict_path::query::Fetch::get_item(&mut self.#members, idx) }, )*
                 }
-            }).collect::<Result<Vec<_>, _>>()?;
+            }
+        }
 
-            Ok(quote::quote! {
-                #vis enum #query_ident {
-                    #(#query_variants,)*
+        impl #as_query_generics #edict_path::query::AsQuery for #as_query_ty {
+            type Query = #query_ident;
+        }
+
+        impl #as_query_generics #edict_path::query::DefaultQuery for #as_query_ty
+        where
+            for<'x> #query_ident: #edict_path::query::DefaultQuery,
+        {
+            #[inline(always)]
+            fn default_query() -> #query_ident {
+                <#query_ident as #edict_path::query::DefaultQuery>::default_query()
+            }
+        }
+
+        impl #edict_path::query::AsQuery for #query_ident {
+            type Query = Self;
+        }
+
+        impl #edict_path::query::IntoQuery for #query_ident {
+            #[inline(always)]
+            fn into_query(self) -> Self {
+                self
+            }
+        }
+
+        impl #edict_path::query::DefaultQuery for #query_ident
+        where
+            #( for<'x> #query_types: #edict_path::query::DefaultQuery, )*
+        {
+            #[inline(always)]
+            fn default_query() -> Self {
+                #query_ident {
+                    #( #members: <#query_types as #edict_path::query::DefaultQuery>::default_query(), )*
                 }
-            })
+            }
+        }
+
+        impl #edict_path::system::QueryArg for #query_ident
+        where
+            #( for<'x> #query_types: #edict_path::system::QueryArg, )*
+        {
+            #[inline(always)]
+            fn new() -> Self {
+                #query_ident {
+                    #( #members: <#query_types as #edict_path::system::QueryArg>::new(), )*
+                }
+            }
+
+            #[inline(always)]
+            fn before(&mut self, world: &#edict_path::world::World) {
+                #( #edict_path::system::QueryArg::before(&mut self.#members, world); )*
+            }
+
+            #[inline(always)]
+            fn after(&mut self, world: &#edict_path::world::World) {
+                #( #edict_path::system::QueryArg::after(&mut self.#members, world); )*
+            }
+        }
+
+        unsafe impl #edict_path::query::Query for #query_ident {
+            type Item<'a> = #item_ty;
+            type Fetch<'a> = #fetch_ident<'a>;
+
+            const MUTABLE: bool = false #( || <#query_types as #edict_path::query::Query>::MUTABLE )*;
+            const FILTERS_ENTITIES: bool = false #( || <#query_types as #edict_path::query::Query>::FILTERS_ENTITIES )*;
+
+            #[inline(always)]
+            fn component_access(
+                &self,
+                comp: &#edict_path::component::ComponentInfo,
+            ) -> ::core::result::Result<::core::option::Option<#edict_path::Access>, #edict_path::query::WriteAlias> {
+                let mut result = ::core::option::Option::None;
+                #(
+                    result = match (result, #edict_path::query::Query::component_access(&self.#members, comp)?) {
+                        (::core::option::Option::None, one) | (one, ::core::option::Option::None) => one,
+                        (::core::option::Option::Some(#edict_path::Access::Read), ::core::option::Option::Some(#edict_path::Access::Read)) => ::core::option::Option::Some(#edict_path::Access::Read),
+                        _ => return ::core::result::Result::Err(#edict_path::query::WriteAlias),
+                    };
+                )*
+                ::core::result::Result::Ok(result)
+            }
+
+            #[inline(always)]
+            fn visit_archetype(&self, archetype: &#edict_path::archetype::Archetype) -> bool {
+                true #( && #edict_path::query::Query::visit_archetype(&self.#members, archetype) )*
+            }
+
+            #[inline(always)]
+            unsafe fn access_archetype(
+                &self,
+                archetype: &#edict_path::archetype::Archetype,
+                mut f: impl ::core::ops::FnMut(::core::any::TypeId, #edict_path::Access),
+            ) {
+                #( unsafe { #edict_path::query::Query::access_archetype(&self.#members, archetype, &mut f) } )*
+            }
+
+            #[inline(always)]
+            unsafe fn visit_archetype_late(&self, archetype: &#edict_path::archetype::Archetype) -> bool {
+                unsafe { true #( && #edict_path::query::Query::visit_archetype_late(&self.#members, archetype) )* }
+            }
+
+            #[inline(always)]
+            unsafe fn fetch<'a>(
+                &self,
+                arch_idx: u32,
+                archetype: &'a #edict_path::archetype::Archetype,
+                epoch: #edict_path::epoch::EpochId,
+            ) -> #fetch_ident<'a> {
+                #fetch_ident {
+                    #( #members: unsafe { #edict_path::query::Query::fetch(&self.#members, arch_idx, archetype, epoch) }, )*
+                }
+            }
+
+            #[inline(always)]
+            fn reserved_entity_item<'a>(
+                &self,
+                id: #edict_path::entity::EntityId,
+                idx: u32,
+            ) -> ::core::option::Option<#item_ty> {
+                ::core::option::Option::Some(#ident {
+                    #( #members: #edict_path::query::Query::reserved_entity_item(&self.#members, id, idx)?, )*
+                })
+            }
+        }
+
+        unsafe impl #edict_path::query::ImmutableQuery for #query_ident
+        where
+            #( for<'x> #query_types: #edict_path::query::ImmutableQuery, )*
+        {
+        }
+
+        unsafe impl #edict_path::query::SendQuery for #query_ident
+        where
+            #( for<'x> #query_types: #edict_path::query::SendQuery, )*
+        {
+        }
+    })
+}
+
+/// Returns lifetime of the item struct if any.
+/// Only single lifetime parameter is supported.
+fn item_lifetime(generics: &syn::Generics) -> syn::Result<Option<&syn::Lifetime>> {
+    let mut lifetime = None;
+
+    for param in &generics.params {
+        match param {
+            syn::GenericParam::Lifetime(param) if lifetime.is_none() => {
+                lifetime = Some(&param.lifetime);
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "Deriving `Query` is supported only for structs with at most one lifetime parameter",
+                ))
+            }
+        }
+    }
+
+    if let Some(where_clause) = &generics.where_clause {
+        return Err(syn::Error::new_spanned(
+            where_clause,
+            "Deriving `Query` is not supported for structs with where clause",
+        ));
+    }
+
+    Ok(lifetime)
+}
+
+struct ReplaceLifetime<'a> {
+    lifetime: &'a syn::Lifetime,
+}
+
+impl VisitMut for ReplaceLifetime<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime == self.lifetime {
+            *lifetime = syn::Lifetime::new("'static", lifetime.span());
         }
     }
 }
@@ -23,13 +23,6 @@ pub fn system(mut item: syn::ItemFn, edict_path: &syn::Path) -> syn::Result<Toke
         ));
     }
 
-    if item.sig.output != syn::ReturnType::Default {
-        return Err(syn::Error::new_spanned(
-            item.sig.output,
-            "functions with return types may not be used with `#[system]` attribute",
-        ));
-    }
-
     let mut checks = Vec::new();
 
     // let where_clause = item.sig.generics.make_where_clause();
@@ -59,12 +52,34 @@ pub fn system(mut item: syn::ItemFn, edict_path: &syn::Path) -> syn::Result<Toke
 
     let ident = &item.sig.ident;
 
-    checks.push(
-        syn::parse_quote_spanned!(ident.span() => #edict_path::private::is_fn_system(#ident);),
-    );
+    match item.sig.output {
+        syn::ReturnType::Default => checks.push(
+            syn::parse_quote_spanned!(ident.span() => #edict_path::private::is_fn_system(#ident);),
+        ),
+        // Functions that return `Result` must be fallible systems.
+        syn::ReturnType::Type(_, ref ty) if is_result(ty) => checks.push(
+            syn::parse_quote_spanned!(ty.span() => #edict_path::private::is_fallible_fn_system(#ident);),
+        ),
+        // Functions that return other values may be used only as sources of pipes.
+        syn::ReturnType::Type(_, ref ty) => checks.push(
+            syn::parse_quote_spanned!(ty.span() => #edict_path::private::is_fn_pipe_source(#ident);),
+        ),
+    }
 
     checks.append(&mut item.block.stmts);
     item.block.stmts = checks;
 
     Ok(quote::quote!(#item))
 }
+
+/// Checks if type is spelled as `Result<..>`, with any path prefix.
+fn is_result(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => {
+            matches!(path.path.segments.last(), Some(segment) if segment.ident == "Result")
+        }
+        syn::Type::Paren(paren) => is_result(&paren.elem),
+        syn::Type::Group(group) => is_result(&group.elem),
+        _ => false,
+    }
+}
@@ -14,15 +14,28 @@ pub fn derive_relation(item: TokenStream) -> TokenStream {
     edict_proc_lib::derive_relation(item.into(), &path, path.get_ident().unwrap()).into()
 }
 
+/// Derives query for a struct which fields are query items.
+///
+/// Generates query type named after the struct with `Query` suffix
+/// and fetch type with `Fetch` suffix.
+/// Struct may have at most one lifetime parameter and no type parameters.
+#[proc_macro_derive(Query)]
+pub fn derive_query(item: TokenStream) -> TokenStream {
+    let path: syn::Path = syn::parse_quote!(edict);
+    edict_proc_lib::derive_query(item.into(), &path, path.get_ident().unwrap()).into()
+}
+
 /// This attribute adds checks for system functions.
 /// Only applicable to function items.
 ///
 /// Generates compilation error if function has type parameters,
-/// is async, unsafe, has return type or is a method.
+/// is async, unsafe or is a method.
 ///
 /// Checks that all function arguments are valid system arguments.
 ///
 /// And finally checks that function is a system.
+/// Function that returns `Result` must be a fallible system.
+/// Function that returns other value must be a source of a pipe.
 #[proc_macro_attribute]
 pub fn system(attr: TokenStream, item: TokenStream) -> TokenStream {
     if !attr.is_empty() {
@@ -1,9 +1,13 @@
 use alloc::sync::Arc;
-use amity::{flip_queue::FlipQueue, ring_buffer::RingBuffer};
+use amity::{flip_queue::FlipQueue, ring_buffer::RingBuffer, Spin};
 use core::{
     any::TypeId,
+    fmt,
+    future::Future,
     iter::FusedIterator,
-    sync::atomic::{AtomicBool, Ordering},
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
 };
 
 use crate::{
@@ -21,8 +25,124 @@ struct Shared {
     queue: FlipQueue<ActionFn<'static>>,
     non_empty: AtomicBool,
     connected: AtomicBool,
+
+    /// Maximum number of pending actions.
+    /// `usize::MAX` for unbounded channel.
+    capacity: usize,
+
+    /// Number of actions sent but not yet executed.
+    pending: AtomicUsize,
+    executed: AtomicU64,
+    dropped: AtomicU64,
+
+    /// Senders blocked on full channel wait here.
+    #[cfg(feature = "std")]
+    space_lock: parking_lot::Mutex<()>,
+    #[cfg(feature = "std")]
+    space: parking_lot::Condvar,
+}
+
+impl Shared {
+    #[inline(always)]
+    fn is_bounded(&self) -> bool {
+        self.capacity != usize::MAX
+    }
+
+    /// Reserves a slot for an action.
+    /// Returns `false` if channel is full.
+    #[inline(always)]
+    fn reserve(&self) -> bool {
+        if !self.is_bounded() {
+            self.pending.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+
+        let mut pending = self.pending.load(Ordering::Relaxed);
+        loop {
+            if pending >= self.capacity {
+                return false;
+            }
+            match self.pending.compare_exchange_weak(
+                pending,
+                pending + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => pending = actual,
+            }
+        }
+    }
+
+    /// Wakes senders blocked on full channel.
+    #[inline(always)]
+    fn notify_space(&self) {
+        #[cfg(feature = "std")]
+        if self.is_bounded() {
+            let _guard = self.space_lock.lock();
+            self.space.notify_all();
+        }
+    }
 }
 
+/// Counters of the action channel bound to a [`World`].
+///
+/// Returned by [`World::action_channel_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ActionChannelStats {
+    /// Number of actions sent but not yet executed.
+    pub pending: usize,
+
+    /// Total number of executed actions.
+    pub executed: u64,
+
+    /// Total number of actions dropped because channel was full.
+    pub dropped: u64,
+}
+
+/// Error returned by `try_*` methods of [`ActionSender`]
+/// when the channel is full or disconnected.
+/// Contains the value that failed to be sent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// Channel is at full capacity.
+    Full(T),
+
+    /// Bound [`World`] is dropped.
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value that failed to be sent.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(value) | TrySendError::Disconnected(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Action channel is full"),
+            TrySendError::Disconnected(_) => f.write_str("Action channel is disconnected"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for TrySendError<T> {}
+
 pub(crate) struct ActionChannel {
     shared: Arc<Shared>,
     spare_buffer: RingBuffer<ActionFn<'static>>,
@@ -31,17 +151,36 @@ pub(crate) struct ActionChannel {
 impl Drop for ActionChannel {
     fn drop(&mut self) {
         self.shared.connected.store(false, Ordering::Relaxed);
+
+        // Drop actions that will never be executed.
+        // This resolves pending `ActionResponse`s.
+        self.shared
+            .queue
+            .drain_locking(|drain| drain.for_each(drop));
+
+        // Unblock senders waiting for capacity.
+        self.shared.notify_space();
     }
 }
 
 impl ActionChannel {
+    /// Creates new channel.
+    /// If `capacity` is `None` channel is unbounded.
     #[inline(always)]
-    pub fn new() -> Self {
+    pub fn new(capacity: Option<usize>) -> Self {
         ActionChannel {
             shared: Arc::new(Shared {
                 queue: FlipQueue::new(),
                 non_empty: AtomicBool::new(false),
                 connected: AtomicBool::new(true),
+                capacity: capacity.unwrap_or(usize::MAX),
+                pending: AtomicUsize::new(0),
+                executed: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+                #[cfg(feature = "std")]
+                space_lock: parking_lot::Mutex::new(()),
+                #[cfg(feature = "std")]
+                space: parking_lot::Condvar::new(),
             }),
             spare_buffer: RingBuffer::new(),
         }
@@ -65,7 +204,28 @@ impl ActionChannel {
 
     #[inline(always)]
     pub(crate) fn pop(&mut self) -> Option<ActionFn<'static>> {
-        self.spare_buffer.pop()
+        match self.spare_buffer.pop() {
+            None => {
+                // All fetched actions are executed.
+                self.shared.notify_space();
+                None
+            }
+            Some(action) => {
+                self.shared.pending.fetch_sub(1, Ordering::Relaxed);
+                self.shared.executed.fetch_add(1, Ordering::Relaxed);
+                Some(action)
+            }
+        }
+    }
+
+    /// Returns channel counters.
+    #[inline(always)]
+    pub fn stats(&self) -> ActionChannelStats {
+        ActionChannelStats {
+            pending: self.shared.pending.load(Ordering::Relaxed),
+            executed: self.shared.executed.load(Ordering::Relaxed),
+            dropped: self.shared.dropped.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -81,6 +241,14 @@ impl ActionChannel {
 /// If bound [`World`] is dropped, encoded actions will not be executed.
 /// See [`ActionSender::is_connected`](ActionSender::is_connected) to check
 /// if the channel is still connected to a world.
+///
+/// Channel may be bounded with [`WorldBuilder::with_action_channel_capacity`].
+/// When bounded channel is full, actions encoded with non-`try` methods are dropped
+/// and counted in [`ActionChannelStats::dropped`].
+/// Use `try_*` methods to handle full channel
+/// or `*_blocking` methods to wait until [`World::execute_received_actions`] makes space.
+///
+/// [`WorldBuilder::with_action_channel_capacity`]: crate::world::WorldBuilder::with_action_channel_capacity
 #[derive(Clone)]
 pub struct ActionSender {
     shared: Arc<Shared>,
@@ -117,7 +285,7 @@ impl ActionSender {
         I: IntoIterator,
         I::Item: ComponentBundle + Send + 'static,
     {
-        self.push_fn(|world| {
+        self.push_required(|world| {
             world.ensure_bundle_registered::<I::Item>();
         });
 
@@ -266,9 +434,219 @@ impl ActionSender {
         self.push_fn(fun)
     }
 
+    /// Encodes a custom action with a closure that takes mutable reference to `World`
+    /// and returns a value.
+    ///
+    /// Returns [`ActionResponse`] that resolves to the value
+    /// after the action is executed with [`World::execute_received_actions`].
+    ///
+    /// If bounded channel is full, the action is dropped
+    /// and the response resolves to `None`.
+    /// Use [`ActionSender::try_query`] or [`ActionSender::query_blocking`]
+    /// to handle full channel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, ExampleComponent};
+    /// let mut world = World::new();
+    /// let entity = world.spawn((ExampleComponent,)).id();
+    ///
+    /// let action_sender = world.new_action_sender();
+    ///
+    /// let handle = std::thread::spawn(move || {
+    ///     action_sender
+    ///         .query(move |world| world.try_has_component::<ExampleComponent>(entity))
+    ///         .wait()
+    /// });
+    ///
+    /// while !handle.is_finished() {
+    ///     world.execute_received_actions();
+    /// }
+    ///
+    /// assert_eq!(handle.join().unwrap(), Some(Ok(true)));
+    /// ```
+    #[inline(always)]
+    pub fn query<T>(&self, fun: impl FnOnce(&mut World) -> T + Send + 'static) -> ActionResponse<T>
+    where
+        T: Send + 'static,
+    {
+        let (response, responder) = self.response();
+        self.push_fn(move |world| responder.respond(fun(world)));
+        response
+    }
+
+    /// Encodes a custom action with a closure that takes mutable reference to `World`
+    /// and returns a value.
+    ///
+    /// Fails and returns the closure back if the channel is full or disconnected.
+    #[inline(always)]
+    pub fn try_query<T, F>(&self, fun: F) -> Result<ActionResponse<T>, TrySendError<F>>
+    where
+        F: FnOnce(&mut World) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let fun = self.try_reserve(fun)?;
+        let (response, responder) = self.response();
+        self.push_reserved(move |world| responder.respond(fun(world)));
+        Ok(response)
+    }
+
+    /// Encodes a custom action with a closure that takes mutable reference to `World`
+    /// and returns a value.
+    ///
+    /// Blocks current thread while the channel is full.
+    /// Response resolves to `None` if the channel gets disconnected.
+    ///
+    /// This will block forever if called on the thread that owns the [`World`]
+    /// because actions can't be executed while the thread is blocked.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn query_blocking<T>(
+        &self,
+        fun: impl FnOnce(&mut World) -> T + Send + 'static,
+    ) -> ActionResponse<T>
+    where
+        T: Send + 'static,
+    {
+        let (response, responder) = self.response();
+        self.push_fn_blocking(move |world| responder.respond(fun(world)));
+        response
+    }
+
+    #[inline(always)]
+    fn response<T>(&self) -> (ActionResponse<T>, Responder<T>) {
+        let slot = Arc::new(Spin::new(ResponseSlot {
+            value: None,
+            done: false,
+            waker: None,
+            #[cfg(feature = "std")]
+            thread: None,
+        }));
+
+        let responder = Responder { slot: slot.clone() };
+        let response = ActionResponse {
+            slot,
+            shared: self.shared.clone(),
+        };
+        (response, responder)
+    }
+
+    /// Encodes an action to spawn an entity with provided bundle.
+    ///
+    /// Fails and returns the bundle back if the channel is full or disconnected.
+    #[inline(always)]
+    pub fn try_spawn<B>(&self, bundle: B) -> Result<(), TrySendError<B>>
+    where
+        B: DynamicComponentBundle + Send + 'static,
+    {
+        let bundle = self.try_reserve(bundle)?;
+        self.push_reserved(move |world| {
+            let _ = world.spawn(bundle);
+        });
+        Ok(())
+    }
+
+    /// Encodes a custom action with a closure that takes mutable reference to `World`.
+    ///
+    /// Fails and returns the closure back if the channel is full or disconnected.
+    #[inline(always)]
+    pub fn try_closure<F>(&self, fun: F) -> Result<(), TrySendError<F>>
+    where
+        F: FnOnce(&mut World) + Send + 'static,
+    {
+        let fun = self.try_reserve(fun)?;
+        self.push_reserved(fun);
+        Ok(())
+    }
+
+    /// Encodes an action to spawn an entity with provided bundle.
+    ///
+    /// Blocks current thread while the channel is full.
+    /// Action is dropped if the channel gets disconnected.
+    ///
+    /// This will block forever if called on the thread that owns the [`World`]
+    /// because actions can't be executed while the thread is blocked.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn spawn_blocking<B>(&self, bundle: B)
+    where
+        B: DynamicComponentBundle + Send + 'static,
+    {
+        self.push_fn_blocking(move |world| {
+            let _ = world.spawn(bundle);
+        });
+    }
+
+    /// Encodes a custom action with a closure that takes mutable reference to `World`.
+    ///
+    /// Blocks current thread while the channel is full.
+    /// Action is dropped if the channel gets disconnected.
+    ///
+    /// This will block forever if called on the thread that owns the [`World`]
+    /// because actions can't be executed while the thread is blocked.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn closure_blocking(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
+        self.push_fn_blocking(fun);
+    }
+
     /// Encodes an action to remove component from specified entity.
     #[inline(always)]
     fn push_fn(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
+        if !self.shared.reserve() {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.push_reserved(fun);
+    }
+
+    /// Pushes an internal action that following actions depend on.
+    /// Takes a slot even if channel is full so it is never dropped.
+    #[inline(always)]
+    fn push_required(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
+        self.shared.pending.fetch_add(1, Ordering::Relaxed);
+        self.push_reserved(fun);
+    }
+
+    /// Pushes an action that only speeds up execution of other actions.
+    /// Skipped for bounded channel to not take capacity from actual actions.
+    #[inline(always)]
+    fn push_hint(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
+        if !self.shared.is_bounded() {
+            self.push_fn(fun);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn push_fn_blocking(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
+        if !self.shared.reserve() {
+            let mut guard = self.shared.space_lock.lock();
+            while !self.shared.reserve() {
+                if !self.is_connected() {
+                    return;
+                }
+                self.shared.space.wait(&mut guard);
+            }
+        }
+        self.push_reserved(fun);
+    }
+
+    #[inline(always)]
+    fn try_reserve<T>(&self, value: T) -> Result<T, TrySendError<T>> {
+        if !self.is_connected() {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if !self.shared.reserve() {
+            return Err(TrySendError::Full(value));
+        }
+        Ok(value)
+    }
+
+    /// Pushes action into the queue.
+    /// Slot must be reserved beforehand.
+    #[inline(always)]
+    fn push_reserved(&self, fun: impl FnOnce(&mut World) + Send + 'static) {
         let action = ActionFn::new(fun);
         self.shared.queue.push(action);
         self.shared.non_empty.store(true, Ordering::Relaxed);
@@ -279,6 +657,136 @@ impl ActionSender {
     pub fn is_connected(&self) -> bool {
         self.shared.connected.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the channel is bounded and has no capacity for more actions.
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.shared.pending.load(Ordering::Relaxed) >= self.shared.capacity
+    }
+}
+
+struct ResponseSlot<T> {
+    value: Option<T>,
+    done: bool,
+    waker: Option<Waker>,
+    #[cfg(feature = "std")]
+    thread: Option<std::thread::Thread>,
+}
+
+/// Writing end of the [`ActionResponse`].
+/// Resolves response without value if dropped before responding.
+struct Responder<T> {
+    slot: Arc<Spin<ResponseSlot<T>>>,
+}
+
+impl<T> Drop for Responder<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.resolve(None);
+    }
+}
+
+impl<T> Responder<T> {
+    #[inline(always)]
+    fn respond(self, value: T) {
+        self.resolve(Some(value));
+    }
+
+    fn resolve(&self, value: Option<T>) {
+        let mut slot = self.slot.lock();
+        if slot.done {
+            return;
+        }
+        slot.value = value;
+        slot.done = true;
+
+        let waker = slot.waker.take();
+        #[cfg(feature = "std")]
+        let thread = slot.thread.take();
+        drop(slot);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(thread) = thread {
+            thread.unpark();
+        }
+    }
+}
+
+/// Handle to the value returned by closure sent with [`ActionSender::query`].
+///
+/// Resolves to `Some(value)` after the closure is executed by the bound [`World`].
+/// Resolves to `None` if the [`World`] is dropped before executing the closure
+/// or if the action was dropped because bounded channel was full.
+///
+/// Value can be awaited since this type implements [`Future`],
+/// or waited for by blocking current thread with [`ActionResponse::wait`].
+#[must_use = "Response does nothing unless waited or polled"]
+pub struct ActionResponse<T> {
+    slot: Arc<Spin<ResponseSlot<T>>>,
+    shared: Arc<Shared>,
+}
+
+impl<T> ActionResponse<T> {
+    /// Returns `true` if response is resolved.
+    /// That is, closure was executed or will never be executed.
+    #[inline(always)]
+    pub fn is_ready(&self) -> bool {
+        self.slot.lock().done || !self.shared.connected.load(Ordering::Relaxed)
+    }
+
+    /// Blocks current thread until response is resolved.
+    ///
+    /// Returns `None` if the [`World`] was dropped before executing the closure
+    /// or if the action was dropped because bounded channel was full.
+    ///
+    /// This will block forever if called on the thread that owns the [`World`]
+    /// because the closure can't be executed while the thread is blocked.
+    #[cfg(feature = "std")]
+    pub fn wait(self) -> Option<T> {
+        loop {
+            {
+                let mut slot = self.slot.lock();
+                if slot.done {
+                    return slot.value.take();
+                }
+                slot.thread = Some(std::thread::current());
+            }
+
+            if !self.shared.connected.load(Ordering::Relaxed) {
+                return self.slot.lock().value.take();
+            }
+
+            std::thread::park();
+        }
+    }
+}
+
+impl<T> Future for ActionResponse<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let me = self.get_mut();
+        {
+            let mut slot = me.slot.lock();
+            if slot.done {
+                return Poll::Ready(slot.value.take());
+            }
+            match &mut slot.waker {
+                Some(waker) if waker.will_wake(cx.waker()) => {}
+                waker => *waker = Some(cx.waker().clone()),
+            }
+        }
+
+        if !me.shared.connected.load(Ordering::Relaxed) {
+            return Poll::Ready(me.slot.lock().value.take());
+        }
+
+        Poll::Pending
+    }
 }
 
 /// Spawning iterator. Produced by [`World::spawn_batch`].
@@ -330,7 +838,7 @@ where
         F: FnMut(T, ()) -> T,
     {
         let additional = iter_reserve_hint(&self.bundles);
-        self.sender.push_fn(move |world| {
+        self.sender.push_hint(move |world| {
             world.spawn_reserve::<B>(additional);
         });
 
@@ -350,7 +858,7 @@ where
         // Hence we should reserve space in archetype here.
 
         let additional = iter_reserve_hint(&self.bundles);
-        self.sender.push_fn(move |world| {
+        self.sender.push_hint(move |world| {
             world.spawn_reserve::<B>(additional);
         });
 
@@ -394,7 +902,7 @@ where
         F: FnMut(T, ()) -> T,
     {
         let additional = iter_reserve_hint(&self.bundles);
-        self.sender.push_fn(move |world| {
+        self.sender.push_hint(move |world| {
             world.spawn_reserve::<B>(additional);
         });
 
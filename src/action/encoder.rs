@@ -7,7 +7,7 @@ use crate::{
     bundle::{Bundle, ComponentBundle, DynamicBundle, DynamicComponentBundle},
     component::{Component, ComponentInfo, ComponentRegistry},
     entity::{Entity, EntityId, EntityLoc, EntitySet},
-    relation::Relation,
+    relation::{ChildOf, Relation},
     type_id,
     world::{iter_reserve_hint, World},
 };
@@ -83,6 +83,36 @@ impl<'a> ActionEncoder<'a> {
         entity
     }
 
+    /// Allocates new entity id and encodes an action to insert bundle to the entity.
+    /// Then encodes actions to spawn children of the entity using provided closure.
+    ///
+    /// Each child spawned with [`ChildEncoder`] gets [`ChildOf`] relation to the entity.
+    #[inline(always)]
+    pub fn spawn_with_children<B>(
+        &mut self,
+        bundle: B,
+        f: impl FnOnce(&mut ChildEncoder<'_>),
+    ) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + Send + 'static,
+    {
+        let entity = self.entities.alloc();
+        self.insert_bundle(entity, bundle);
+        self.with_children(entity, f);
+        entity
+    }
+
+    /// Encodes actions to spawn children of the specified entity using provided closure.
+    ///
+    /// Each child spawned with [`ChildEncoder`] gets [`ChildOf`] relation to the entity.
+    #[inline(always)]
+    pub fn with_children(&mut self, parent: impl Entity, f: impl FnOnce(&mut ChildEncoder<'_>)) {
+        f(&mut ChildEncoder {
+            parent: parent.id(),
+            encoder: self.reborrow(),
+        });
+    }
+
     /// Returns an iterator which encodes action to spawn and yield entities
     /// using bundles yielded from provided bundles iterator.
     #[inline(always)]
@@ -530,6 +560,87 @@ where
 {
 }
 
+/// Encoder for actions that spawn children of an entity.
+/// Each spawned entity gets [`ChildOf`] relation to the parent.
+///
+/// Provided to the closure passed to [`ActionEncoder::spawn_with_children`] and [`ActionEncoder::with_children`].
+pub struct ChildEncoder<'a> {
+    parent: EntityId,
+    encoder: ActionEncoder<'a>,
+}
+
+impl<'a> ChildEncoder<'a> {
+    /// Returns id of the parent entity.
+    #[inline(always)]
+    pub fn parent(&self) -> EntityId {
+        self.parent
+    }
+
+    /// Returns encoder that records actions into the same buffer.
+    #[inline(always)]
+    pub fn encoder(&mut self) -> ActionEncoder<'_> {
+        self.encoder.reborrow()
+    }
+
+    /// Allocates new entity id and encodes actions to insert component to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn_one<T>(&mut self, component: T) -> EntityLoc<'_>
+    where
+        T: Component + Send + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert(child, component);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn<B>(&mut self, bundle: B) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + Send + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn_external<B>(&mut self, bundle: B) -> EntityLoc<'_>
+    where
+        B: DynamicBundle + Send + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_external_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    /// Then encodes actions to spawn children of the new entity using provided closure.
+    #[inline(always)]
+    pub fn spawn_with_children<B>(
+        &mut self,
+        bundle: B,
+        f: impl FnOnce(&mut ChildEncoder<'_>),
+    ) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + Send + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        self.encoder.with_children(child, f);
+        child
+    }
+}
+
 /// Encoder for actions that require mutable access to [`World`],
 /// like spawning/despawning entities and inserting/removing/dropping components and relations.
 ///
@@ -599,6 +710,40 @@ impl<'a> LocalActionEncoder<'a> {
         entity
     }
 
+    /// Allocates new entity id and encodes an action to insert bundle to the entity.
+    /// Then encodes actions to spawn children of the entity using provided closure.
+    ///
+    /// Each child spawned with [`LocalChildEncoder`] gets [`ChildOf`] relation to the entity.
+    #[inline(always)]
+    pub fn spawn_with_children<B>(
+        &mut self,
+        bundle: B,
+        f: impl FnOnce(&mut LocalChildEncoder<'_>),
+    ) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + 'static,
+    {
+        let entity = self.entities.alloc();
+        self.insert_bundle(entity, bundle);
+        self.with_children(entity, f);
+        entity
+    }
+
+    /// Encodes actions to spawn children of the specified entity using provided closure.
+    ///
+    /// Each child spawned with [`LocalChildEncoder`] gets [`ChildOf`] relation to the entity.
+    #[inline(always)]
+    pub fn with_children(
+        &mut self,
+        parent: impl Entity,
+        f: impl FnOnce(&mut LocalChildEncoder<'_>),
+    ) {
+        f(&mut LocalChildEncoder {
+            parent: parent.id(),
+            encoder: self.reborrow(),
+        });
+    }
+
     /// Returns an iterator which encodes action to spawn and yield entities
     /// using bundles yielded from provided bundles iterator.
     #[inline(always)]
@@ -1061,3 +1206,84 @@ where
     B: Bundle + Send + 'static,
 {
 }
+
+/// Encoder for actions that spawn children of an entity.
+/// Each spawned entity gets [`ChildOf`] relation to the parent.
+///
+/// Provided to the closure passed to [`LocalActionEncoder::spawn_with_children`] and [`LocalActionEncoder::with_children`].
+pub struct LocalChildEncoder<'a> {
+    parent: EntityId,
+    encoder: LocalActionEncoder<'a>,
+}
+
+impl<'a> LocalChildEncoder<'a> {
+    /// Returns id of the parent entity.
+    #[inline(always)]
+    pub fn parent(&self) -> EntityId {
+        self.parent
+    }
+
+    /// Returns encoder that records actions into the same buffer.
+    #[inline(always)]
+    pub fn encoder(&mut self) -> LocalActionEncoder<'_> {
+        self.encoder.reborrow()
+    }
+
+    /// Allocates new entity id and encodes actions to insert component to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn_one<T>(&mut self, component: T) -> EntityLoc<'_>
+    where
+        T: Component + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert(child, component);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn<B>(&mut self, bundle: B) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    #[inline(always)]
+    pub fn spawn_external<B>(&mut self, bundle: B) -> EntityLoc<'_>
+    where
+        B: DynamicBundle + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_external_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        child
+    }
+
+    /// Allocates new entity id and encodes actions to insert bundle to the entity
+    /// and to add [`ChildOf`] relation to the parent.
+    /// Then encodes actions to spawn children of the new entity using provided closure.
+    #[inline(always)]
+    pub fn spawn_with_children<B>(
+        &mut self,
+        bundle: B,
+        f: impl FnOnce(&mut LocalChildEncoder<'_>),
+    ) -> EntityLoc<'_>
+    where
+        B: DynamicComponentBundle + 'static,
+    {
+        let child = self.encoder.entities.alloc();
+        self.encoder.insert_bundle(child, bundle);
+        self.encoder.insert_relation(child, ChildOf, self.parent);
+        self.encoder.with_children(child, f);
+        child
+    }
+}
@@ -19,8 +19,11 @@ tiny_fn::tiny_fn! {
 
 pub use self::{
     buffer::{ActionBuffer, ActionBufferSliceExt, LocalActionBuffer},
-    channel::{ActionSender, SpawnBatchSender},
-    encoder::{ActionEncoder, LocalActionEncoder, LocalSpawnBatch, SpawnBatch},
+    channel::{ActionChannelStats, ActionResponse, ActionSender, SpawnBatchSender, TrySendError},
+    encoder::{
+        ActionEncoder, ChildEncoder, LocalActionEncoder, LocalChildEncoder, LocalSpawnBatch,
+        SpawnBatch,
+    },
 };
 
 pub(crate) use self::channel::ActionChannel;
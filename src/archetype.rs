@@ -15,6 +15,7 @@ use core::{
     mem::{self, size_of, ManuallyDrop, MaybeUninit},
     ops::Deref,
     ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use atomicell::borrow::{
@@ -23,8 +24,13 @@ use atomicell::borrow::{
 use hashbrown::HashMap;
 
 use crate::{
-    action::LocalActionEncoder, bundle::DynamicBundle, component::ComponentInfo, entity::EntityId,
-    epoch::EpochId, hash::NoOpHasherBuilder, type_id, Access,
+    action::LocalActionEncoder,
+    bundle::DynamicBundle,
+    component::{ComponentInfo, ValueCompare},
+    entity::EntityId,
+    epoch::EpochId,
+    hash::NoOpHasherBuilder,
+    type_id, Access,
 };
 
 /// Maximum index of the entity in the archetype.
@@ -35,6 +41,129 @@ pub(crate) struct ComponentData {
     pub epoch: EpochId,
     pub entity_epochs: Box<[EpochId]>,
     pub chunk_epochs: Box<[EpochId]>,
+
+    /// Entity epochs at the moment snapshots were taken.
+    /// `None` for entities without snapshot.
+    /// Empty unless compare-on-write is enabled for the component.
+    pub snapshot_epochs: Box<[Option<EpochId>]>,
+
+    /// Buffer of snapshot values taken before writes,
+    /// laid out same way as component values.
+    /// Allocated only if compare-on-write is enabled for the component.
+    pub snapshots: NonNull<u8>,
+
+    /// Set when at least one snapshot is taken.
+    pub has_snapshots: AtomicBool,
+}
+
+/// Takes snapshots of compare-on-write component values before writes.
+///
+/// Entities write to their own snapshot slots,
+/// so copies of the writer may be used from multiple threads.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SnapshotWriter<'a> {
+    compare: ValueCompare,
+    size: usize,
+    epochs: NonNull<Option<EpochId>>,
+    snapshots: NonNull<u8>,
+    has_snapshots: &'a AtomicBool,
+}
+
+impl<'a> SnapshotWriter<'a> {
+    /// Returns writer if compare-on-write is enabled for the component.
+    /// Resolves snapshots taken by previous writers.
+    ///
+    /// # Safety
+    ///
+    /// Caller must have exclusive access to the component.
+    #[inline(always)]
+    pub unsafe fn new(component: &'a ArchetypeComponent) -> Option<Self> {
+        let compare = component.info.value_compare()?;
+        unsafe {
+            component.resolve_snapshots();
+        }
+        let data = unsafe { component.data_mut() };
+
+        Some(SnapshotWriter {
+            compare,
+            size: component.info.layout().size(),
+            epochs: unsafe { NonNull::new_unchecked(data.snapshot_epochs.as_mut_ptr()) },
+            snapshots: data.snapshots,
+            has_snapshots: &data.has_snapshots,
+        })
+    }
+
+    /// Takes snapshot of the value before it is written,
+    /// unless snapshot for the entity is already taken.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be in bounds and `value` must point to the entity's component.
+    /// Caller must have exclusive access to the entity's component.
+    #[inline(always)]
+    pub unsafe fn record(&self, idx: u32, value: NonNull<u8>, epoch: EpochId) {
+        let slot = unsafe { &mut *self.epochs.as_ptr().add(idx as usize) };
+        if slot.is_none() {
+            let snapshot = unsafe {
+                NonNull::new_unchecked(self.snapshots.as_ptr().add(idx as usize * self.size))
+            };
+            unsafe { self.compare.snapshot(value, snapshot) };
+            *slot = Some(epoch);
+            self.has_snapshots.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Reads effective epochs of compare-on-write component.
+#[derive(Clone, Copy)]
+pub(crate) struct SnapshotReader {
+    compare: ValueCompare,
+    size: usize,
+    epochs: NonNull<Option<EpochId>>,
+    snapshots: NonNull<u8>,
+}
+
+impl SnapshotReader {
+    /// Returns reader if compare-on-write is enabled for the component.
+    ///
+    /// # Safety
+    ///
+    /// Caller must have shared access to the component.
+    #[inline(always)]
+    pub unsafe fn new(component: &ArchetypeComponent) -> Option<Self> {
+        let compare = component.info.value_compare()?;
+        let data = unsafe { component.data() };
+
+        Some(SnapshotReader {
+            compare,
+            size: component.info.layout().size(),
+            epochs: unsafe { NonNull::new_unchecked(data.snapshot_epochs.as_ptr().cast_mut()) },
+            snapshots: data.snapshots,
+        })
+    }
+
+    /// Returns epoch of the entity's component ignoring writes that left value unchanged.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be in bounds and `value` must point to the entity's component.
+    /// Caller must have shared access to the component.
+    #[inline(always)]
+    pub unsafe fn epoch(&self, idx: u32, value: NonNull<u8>, epoch: EpochId) -> EpochId {
+        match unsafe { *self.epochs.as_ptr().add(idx as usize) } {
+            Some(snapshot_epoch) => {
+                let snapshot = unsafe {
+                    NonNull::new_unchecked(self.snapshots.as_ptr().add(idx as usize * self.size))
+                };
+                if unsafe { self.compare.eq(value, snapshot) } {
+                    snapshot_epoch
+                } else {
+                    epoch
+                }
+            }
+            None => epoch,
+        }
+    }
 }
 
 pub(crate) struct ArchetypeComponent {
@@ -80,6 +209,57 @@ impl ArchetypeComponent {
     pub unsafe fn data_mut(&self) -> &mut ComponentData {
         unsafe { &mut *self.data.get() }
     }
+
+    /// Drops taken snapshots, restoring entity epochs
+    /// where component value is equal to the snapshot.
+    /// Chunk and component epochs are recomputed from restored entity epochs.
+    ///
+    /// # Safety
+    ///
+    /// Caller must have exclusive access to the component.
+    #[inline(always)]
+    pub unsafe fn resolve_snapshots(&self) {
+        let Some(compare) = self.info.value_compare() else {
+            return;
+        };
+
+        let data = unsafe { self.data_mut() };
+        if !*data.has_snapshots.get_mut() {
+            return;
+        }
+        *data.has_snapshots.get_mut() = false;
+
+        let size = self.info.layout().size();
+        let mut restored_chunk = None;
+        for (idx, slot) in data.snapshot_epochs.iter_mut().enumerate() {
+            if let Some(epoch) = slot.take() {
+                let value = unsafe { NonNull::new_unchecked(data.ptr.as_ptr().add(idx * size)) };
+                let snapshot =
+                    unsafe { NonNull::new_unchecked(data.snapshots.as_ptr().add(idx * size)) };
+                if unsafe { compare.eq(value, snapshot) } {
+                    data.entity_epochs[idx] = epoch;
+
+                    // Snapshots are visited in order, so chunks are too.
+                    let chunk = chunk_idx(idx as u32);
+                    if let Some(last) = restored_chunk.filter(|&last| last != chunk) {
+                        recompute_chunk_epoch(&data.entity_epochs, &mut data.chunk_epochs, last);
+                    }
+                    restored_chunk = Some(chunk);
+                }
+                unsafe { compare.drop(snapshot) };
+            }
+        }
+
+        if let Some(last) = restored_chunk {
+            recompute_chunk_epoch(&data.entity_epochs, &mut data.chunk_epochs, last);
+
+            let mut epoch = EpochId::start();
+            for chunk_epoch in data.chunk_epochs.iter() {
+                epoch.update(*chunk_epoch);
+            }
+            data.epoch = epoch;
+        }
+    }
 }
 
 impl ArchetypeComponent {
@@ -90,6 +270,9 @@ impl ArchetypeComponent {
                 epoch: EpochId::start(),
                 chunk_epochs: Box::new([]),
                 entity_epochs: Box::new([]),
+                snapshot_epochs: Box::new([]),
+                snapshots: NonNull::dangling(),
+                has_snapshots: AtomicBool::new(false),
             }),
             lock: new_lock(),
             info: info.clone(),
@@ -97,6 +280,10 @@ impl ArchetypeComponent {
     }
 
     unsafe fn drop(&mut self, cap: usize, len: usize) {
+        unsafe {
+            self.resolve_snapshots();
+        }
+
         let data = self.data.get_mut();
 
         self.info.final_drop(data.ptr, len);
@@ -113,10 +300,22 @@ impl ArchetypeComponent {
             unsafe {
                 dealloc(data.ptr.as_ptr(), layout);
             }
+
+            if self.info.value_compare().is_some() {
+                // Safety: snapshot buffer has the same layout.
+                unsafe {
+                    dealloc(data.snapshots.as_ptr(), layout);
+                }
+            }
         }
     }
 
     unsafe fn grow(&mut self, len: u32, old_cap: u32, new_cap: u32) {
+        // Snapshots are dropped here, so they need not be moved to the new buffer.
+        unsafe {
+            self.resolve_snapshots();
+        }
+
         let data = self.data.get_mut();
 
         debug_assert!(len <= old_cap);
@@ -177,6 +376,42 @@ impl ArchetypeComponent {
         chunk_epochs.reserve_exact((chunks_count(new_cap) - chunks_count(old_cap)) as usize);
         chunk_epochs.resize(chunks_count(new_cap) as usize, EpochId::start());
         data.chunk_epochs = chunk_epochs.into_boxed_slice();
+
+        if self.info.value_compare().is_some() {
+            let mut snapshot_epochs = core::mem::take(&mut data.snapshot_epochs).into_vec();
+            snapshot_epochs.reserve_exact((new_cap - old_cap) as usize);
+            snapshot_epochs.resize(new_cap as usize, None);
+            data.snapshot_epochs = snapshot_epochs.into_boxed_slice();
+
+            if self.info.layout().size() != 0 {
+                // Same layout as the component values buffer above.
+                let new_layout = Layout::from_size_align(
+                    self.info.layout().size() * new_cap as usize,
+                    self.info.layout().align(),
+                )
+                .unwrap();
+
+                let Some(ptr) = NonNull::new(unsafe { alloc(new_layout) }) else {
+                    alloc::alloc::handle_alloc_error(new_layout);
+                };
+
+                if old_cap != 0 {
+                    // Safety: layout of existing allocation.
+                    let old_layout = unsafe {
+                        Layout::from_size_align_unchecked(
+                            self.info.layout().size() * (old_cap as usize),
+                            self.info.layout().align(),
+                        )
+                    };
+
+                    unsafe {
+                        dealloc(data.snapshots.as_ptr(), old_layout);
+                    }
+                }
+
+                data.snapshots = ptr;
+            }
+        }
     }
 }
 
@@ -389,6 +624,8 @@ impl Archetype {
         idx: u32,
         mut encoder: LocalActionEncoder,
     ) -> Option<EntityId> {
+        self.resolve_snapshots();
+
         let entity_idx = idx;
         debug_assert!(entity_idx < self.entities.len() as u32);
         debug_assert_eq!(id, self.entities[entity_idx as usize]);
@@ -458,6 +695,8 @@ impl Archetype {
     ) where
         B: DynamicBundle,
     {
+        self.resolve_snapshots();
+
         let entity_idx = idx;
         debug_assert!(
             bundle.with_ids(|ids| ids.iter().all(|&id| self.components.contains_key(&id)))
@@ -485,6 +724,8 @@ impl Archetype {
     ) where
         T: 'static,
     {
+        self.resolve_snapshots();
+
         let entity_idx = idx;
 
         debug_assert!(self.components.contains_key(&type_id::<T>()));
@@ -592,6 +833,8 @@ impl Archetype {
     where
         B: DynamicBundle,
     {
+        self.resolve_snapshots();
+
         debug_assert!(self.ids().all(|id| dst.components.contains_key(&id)));
         debug_assert!(bundle.with_ids(|ids| ids.iter().all(|&id| dst.components.contains_key(&id))));
 
@@ -670,6 +913,8 @@ impl Archetype {
     where
         T: 'static,
     {
+        self.resolve_snapshots();
+
         debug_assert!(self.ids().all(|id| dst.components.contains_key(&id)));
         debug_assert!(!self.components.contains_key(&type_id::<T>()));
         debug_assert!(dst.components.contains_key(&type_id::<T>()));
@@ -722,6 +967,8 @@ impl Archetype {
     where
         T: 'static,
     {
+        self.resolve_snapshots();
+
         debug_assert!(dst.ids().all(|id| self.components.contains_key(&id)));
         debug_assert!(!dst.components.contains_key(&type_id::<T>()));
         debug_assert!(self.components.contains_key(&type_id::<T>()));
@@ -777,6 +1024,8 @@ impl Archetype {
         src_idx: u32,
         mut encoder: LocalActionEncoder,
     ) -> (u32, Option<EntityId>) {
+        self.resolve_snapshots();
+
         debug_assert!(dst.ids().all(|id| self.components.contains_key(&id)));
 
         let src_entity_idx = src_idx;
@@ -807,6 +1056,18 @@ impl Archetype {
         }
     }
 
+    /// Resolves snapshots of compare-on-write components.
+    /// Must be called before entities are moved within the archetype or replaced.
+    #[inline(always)]
+    fn resolve_snapshots(&mut self) {
+        for component in self.components.values_mut() {
+            // Safety: archetype is borrowed mutably.
+            unsafe {
+                component.resolve_snapshots();
+            }
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn entities(&self) -> &[EntityId] {
         &self.entities
@@ -1043,6 +1304,18 @@ pub(crate) const fn chunk_idx(idx: u32) -> u32 {
     idx >> 8
 }
 
+/// Sets chunk epoch to the latest epoch of entities in the chunk.
+fn recompute_chunk_epoch(entity_epochs: &[EpochId], chunk_epochs: &mut [EpochId], chunk: u32) {
+    let start = (chunk * CHUNK_LEN) as usize;
+    let end = (start + CHUNK_LEN as usize).min(entity_epochs.len());
+
+    let mut epoch = EpochId::start();
+    for entity_epoch in &entity_epochs[start..end] {
+        epoch.update(*entity_epoch);
+    }
+    chunk_epochs[chunk as usize] = epoch;
+}
+
 #[inline(always)]
 pub(crate) const fn chunks_count(entities: u32) -> u32 {
     entities + (CHUNK_LEN - 1) / CHUNK_LEN
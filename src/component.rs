@@ -397,6 +397,72 @@ pub trait Component: Sized + 'static {
     fn borrows() -> Vec<ComponentBorrow> {
         vec![ComponentBorrow::auto::<Self>()]
     }
+
+    /// Returns functions to compare component values on write.
+    /// If `Some`, writes that leave the value unchanged are not reported as modifications.
+    ///
+    /// When using `#[derive(Component)]` add `#[edict(compare_on_write)]` attribute to enable it.
+    #[inline(always)]
+    fn value_compare() -> Option<ValueCompare> {
+        None
+    }
+}
+
+/// Type-erased functions to snapshot and compare component values.
+///
+/// Enables compare-on-write change detection for the component.
+/// Fetches that write the component take a snapshot of the value first.
+/// Snapshots are stored in per-component buffer of the archetype.
+/// If value is equal to the snapshot after the write,
+/// the entity is not reported as modified by [`Modified`] queries.
+/// Each mutable borrow is compared with the value it started with.
+///
+/// [`Modified`]: crate::query::Modified
+#[derive(Clone, Copy, Debug)]
+pub struct ValueCompare {
+    snapshot: unsafe fn(NonNull<u8>, NonNull<u8>),
+    eq: unsafe fn(NonNull<u8>, NonNull<u8>) -> bool,
+    drop: unsafe fn(NonNull<u8>),
+}
+
+impl ValueCompare {
+    /// Returns compare functions for the specified type.
+    #[inline(always)]
+    pub fn of<T>() -> Self
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        ValueCompare {
+            snapshot: |value, snapshot| unsafe {
+                let value = value.cast::<T>().as_ref();
+                snapshot.cast::<T>().as_ptr().write(value.clone());
+            },
+            eq: |value, snapshot| unsafe {
+                *value.cast::<T>().as_ref() == *snapshot.cast::<T>().as_ref()
+            },
+            drop: |snapshot| unsafe {
+                snapshot.cast::<T>().as_ptr().drop_in_place();
+            },
+        }
+    }
+
+    /// Clones the value into uninitialized snapshot slot.
+    #[inline(always)]
+    pub(crate) unsafe fn snapshot(&self, value: NonNull<u8>, snapshot: NonNull<u8>) {
+        unsafe { (self.snapshot)(value, snapshot) }
+    }
+
+    /// Checks if the value is equal to the snapshot.
+    #[inline(always)]
+    pub(crate) unsafe fn eq(&self, value: NonNull<u8>, snapshot: NonNull<u8>) -> bool {
+        unsafe { (self.eq)(value, snapshot) }
+    }
+
+    /// Drops the snapshot in place.
+    #[inline(always)]
+    pub(crate) unsafe fn drop(&self, snapshot: NonNull<u8>) {
+        unsafe { (self.drop)(snapshot) }
+    }
 }
 
 /// Type information required for components.
@@ -431,6 +497,9 @@ pub struct ComponentInfo {
 
     /// An array of possible component borrows.
     borrows: Arc<[ComponentBorrow]>,
+
+    /// Functions for compare-on-write change detection.
+    value_compare: Option<ValueCompare>,
 }
 
 impl ComponentInfo {
@@ -450,6 +519,7 @@ impl ComponentInfo {
             on_replace: Arc::new(DefaultSetHook),
             final_drop: final_drop::<T>,
             borrows: Arc::from(T::borrows()),
+            value_compare: T::value_compare(),
         }
     }
 
@@ -469,6 +539,7 @@ impl ComponentInfo {
             on_replace: Arc::new(ExternalSetHook),
             final_drop: final_drop::<T>,
             borrows: vec![ComponentBorrow::auto::<T>()].into(),
+            value_compare: None,
         }
     }
 
@@ -511,6 +582,13 @@ impl ComponentInfo {
             .map_or(false, |b| b.has_borrow_mut())
     }
 
+    /// Returns functions for compare-on-write change detection
+    /// if enabled for the component.
+    #[inline(always)]
+    pub fn value_compare(&self) -> Option<ValueCompare> {
+        self.value_compare
+    }
+
     #[inline(always)]
     pub(crate) fn drop_one(&self, ptr: NonNull<u8>, id: EntityId, encoder: LocalActionEncoder) {
         unsafe {
@@ -767,6 +845,20 @@ where
         self.name = Some(name);
         self
     }
+
+    /// Enables compare-on-write change detection for this component.
+    ///
+    /// Writes that leave the value unchanged are not reported
+    /// as modifications by [`Modified`] queries.
+    ///
+    /// [`Modified`]: crate::query::Modified
+    pub fn compare_on_write(mut self) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        self.info.as_mut().unwrap().value_compare = Some(ValueCompare::of::<T>());
+        self
+    }
 }
 
 /// Container for [`ComponentInfo`]s.
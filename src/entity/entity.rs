@@ -5,6 +5,7 @@ use crate::{
     component::Component,
     // flow::FlowEntityFn,
     query::{DefaultQuery, ImmutableQuery, IntoQuery, QueryItem},
+    relation::ChildSpawner,
     view::ViewOne,
     world::{World, WorldLocal},
     NoSuchEntity,
@@ -1101,6 +1102,42 @@ impl<'a> EntityRef<'a> {
         })
     }
 
+    /// Spawns children of the entity.
+    ///
+    /// Closure receives [`ChildSpawner`] that spawns entities
+    /// with [`ChildOf`](crate::relation::ChildOf) relation to this entity.
+    ///
+    /// This consumes entity because relation insertion may execute a hook
+    /// which may invalidate the entity reference and even despawn the entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, relation::ChildOf, query::Entities, ExampleComponent};
+    /// let mut world = World::new();
+    /// let parent = world
+    ///     .spawn(())
+    ///     .with_children(|c| {
+    ///         c.spawn((ExampleComponent,));
+    ///         c.spawn_one(ExampleComponent);
+    ///     })
+    ///     .unwrap()
+    ///     .id();
+    ///
+    /// assert_eq!(world.view::<Entities>().relates_to::<ChildOf>(parent).iter().count(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn with_children(self, f: impl FnOnce(&mut ChildSpawner<'_>)) -> Option<Self> {
+        f(&mut ChildSpawner::new(self.id, self.world));
+
+        let loc = self.world.entities().get_location(self.id)?;
+        Some(EntityRef {
+            id: self.id,
+            loc,
+            world: self.world,
+        })
+    }
+
     /// Despawns the entity.
     #[inline(always)]
     pub fn despawn(self) {
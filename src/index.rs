@@ -0,0 +1,385 @@
+//! Secondary indexes over component values.
+//!
+//! Index maps keys computed from component values to entities that have them.
+//! Indexes are registered in the [`World`] with [`World::register_index`]
+//! and accessed with [`World::index`].
+//!
+//! Indexes are kept in sync with the [`World`] automatically.
+//! Each access to an index visits components modified since previous access,
+//! relying on the same epochs that are used for [`Modified`] queries.
+//! Entities that lost the component or were despawned are dropped from the index as well.
+//!
+//! Two index storages are provided.
+//! [`HashIndex`] for keys that implement [`Hash`] and [`Eq`]
+//! and [`OrdIndex`] for keys that implement [`Ord`] and supports range scans.
+//!
+//! # Example
+//!
+//! ```
+//! # use edict::{world::World, component::Component, index::{Index, HashIndex}};
+//! #[derive(Component)]
+//! struct Name(&'static str);
+//!
+//! struct ByName;
+//!
+//! impl Index for ByName {
+//!     type Component = Name;
+//!     type Key = &'static str;
+//!     type Storage = HashIndex<&'static str>;
+//!
+//!     fn key(name: &Name) -> &'static str {
+//!         name.0
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! world.register_index::<ByName>();
+//!
+//! let alice = world.spawn((Name("alice"),)).id();
+//! let bob = world.spawn((Name("bob"),)).id();
+//!
+//! assert_eq!(world.index::<ByName>().get("alice"), [alice]);
+//!
+//! world.get::<&mut Name>(bob).unwrap().0 = "alice";
+//! assert_eq!(world.index::<ByName>().get("alice").len(), 2);
+//! assert!(world.index::<ByName>().get("bob").is_empty());
+//! ```
+//!
+//! [`World`]: crate::world::World
+//! [`World::register_index`]: crate::world::World::register_index
+//! [`World::index`]: crate::world::World::index
+//! [`Modified`]: crate::query::Modified
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    borrow::Borrow,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, RangeBounds},
+};
+
+use hashbrown::HashMap;
+
+use crate::{
+    component::Component,
+    entity::EntityId,
+    epoch::EpochId,
+    query::{Entities, Modified, Read},
+    resources::Res,
+    type_id,
+    world::World,
+};
+
+/// Describes an index over values of a component.
+///
+/// Type implementing this trait is used only as a marker
+/// to register and access the index.
+pub trait Index: 'static {
+    /// Component type which values are indexed.
+    type Component: Component + Sync;
+
+    /// Key type computed from component values.
+    type Key: Clone + Eq + Send + Sync + 'static;
+
+    /// Storage that maps keys to entities.
+    type Storage: IndexStorage<Self::Key>;
+
+    /// Computes key from component value.
+    fn key(component: &Self::Component) -> Self::Key;
+}
+
+/// Storage of the index that maps keys to entities.
+pub trait IndexStorage<K>: Default + Send + Sync + 'static {
+    /// Adds entity with specified key.
+    fn insert(&mut self, key: K, id: EntityId);
+
+    /// Removes entity with specified key.
+    fn remove(&mut self, key: &K, id: EntityId);
+}
+
+/// Index storage based on hash map.
+pub struct HashIndex<K> {
+    map: HashMap<K, Vec<EntityId>>,
+}
+
+impl<K> Default for HashIndex<K> {
+    #[inline(always)]
+    fn default() -> Self {
+        HashIndex {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<K> HashIndex<K>
+where
+    K: Hash + Eq,
+{
+    /// Returns entities with specified key.
+    #[inline(always)]
+    pub fn get<Q>(&self, key: &Q) -> &[EntityId]
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            None => &[],
+            Some(ids) => ids,
+        }
+    }
+
+    /// Returns `true` if at least one entity has specified key.
+    #[inline(always)]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns iterator over all keys and entities in the index.
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, EntityId)> + '_ {
+        self.map
+            .iter()
+            .flat_map(|(key, ids)| ids.iter().map(move |id| (key, *id)))
+    }
+}
+
+impl<K> IndexStorage<K> for HashIndex<K>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+{
+    #[inline(always)]
+    fn insert(&mut self, key: K, id: EntityId) {
+        self.map.entry(key).or_default().push(id);
+    }
+
+    #[inline(always)]
+    fn remove(&mut self, key: &K, id: EntityId) {
+        if let Some(ids) = self.map.get_mut(key) {
+            remove_id(ids, id);
+            if ids.is_empty() {
+                self.map.remove(key);
+            }
+        }
+    }
+}
+
+/// Index storage based on ordered map.
+/// Supports range scans over keys.
+pub struct OrdIndex<K> {
+    map: BTreeMap<K, Vec<EntityId>>,
+}
+
+impl<K> Default for OrdIndex<K> {
+    #[inline(always)]
+    fn default() -> Self {
+        OrdIndex {
+            map: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K> OrdIndex<K>
+where
+    K: Ord,
+{
+    /// Returns entities with specified key.
+    #[inline(always)]
+    pub fn get<Q>(&self, key: &Q) -> &[EntityId]
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.map.get(key) {
+            None => &[],
+            Some(ids) => ids,
+        }
+    }
+
+    /// Returns `true` if at least one entity has specified key.
+    #[inline(always)]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns iterator over keys in specified range and entities with them.
+    /// Keys are visited in ascending order.
+    #[inline(always)]
+    pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = (&K, EntityId)> + '_
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.map
+            .range(range)
+            .flat_map(|(key, ids)| ids.iter().map(move |id| (key, *id)))
+    }
+
+    /// Returns iterator over all keys and entities in the index.
+    /// Keys are visited in ascending order.
+    #[inline(always)]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, EntityId)> + '_ {
+        self.map
+            .iter()
+            .flat_map(|(key, ids)| ids.iter().map(move |id| (key, *id)))
+    }
+}
+
+impl<K> IndexStorage<K> for OrdIndex<K>
+where
+    K: Ord + Send + Sync + 'static,
+{
+    #[inline(always)]
+    fn insert(&mut self, key: K, id: EntityId) {
+        self.map.entry(key).or_default().push(id);
+    }
+
+    #[inline(always)]
+    fn remove(&mut self, key: &K, id: EntityId) {
+        if let Some(ids) = self.map.get_mut(key) {
+            remove_id(ids, id);
+            if ids.is_empty() {
+                self.map.remove(key);
+            }
+        }
+    }
+}
+
+fn remove_id(ids: &mut Vec<EntityId>, id: EntityId) {
+    if let Some(idx) = ids.iter().position(|e| *e == id) {
+        ids.swap_remove(idx);
+    }
+}
+
+/// State of registered index.
+/// Stored in the world as a resource.
+pub(crate) struct IndexState<I: Index> {
+    storage: I::Storage,
+
+    /// Keys of indexed entities.
+    keys: HashMap<EntityId, I::Key>,
+
+    /// Epoch at which index was synced last time.
+    epoch: EpochId,
+
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I> IndexState<I>
+where
+    I: Index,
+{
+    pub fn new() -> Self {
+        IndexState {
+            storage: I::Storage::default(),
+            keys: HashMap::new(),
+            epoch: EpochId::start(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Updates index with changes made in the world since last sync.
+    pub fn sync(&mut self, world: &World) {
+        let after = self.epoch;
+        self.epoch = world.epoch();
+
+        // Inserted and modified components.
+        let view = world.view_with((Entities, Modified::<Read<I::Component>>::new(after)));
+        for (e, component) in view.iter() {
+            let key = I::key(component);
+            match self.keys.insert(e.id(), key.clone()) {
+                Some(old) if old == key => {}
+                Some(old) => {
+                    self.storage.remove(&old, e.id());
+                    self.storage.insert(key, e.id());
+                }
+                None => self.storage.insert(key, e.id()),
+            }
+        }
+        drop(view);
+
+        // Every entity with the component is indexed at this point.
+        // Having more keys means some entities lost the component.
+        let ty = type_id::<I::Component>();
+        let count: usize = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.has_component(ty))
+            .map(|archetype| archetype.len())
+            .sum();
+
+        if self.keys.len() > count {
+            let storage = &mut self.storage;
+            self.keys.retain(|id, key| {
+                let alive = match world.entities().get_location(*id) {
+                    None => false,
+                    Some(loc) => {
+                        loc.arch != u32::MAX
+                            && world.archetypes()[loc.arch as usize].has_component(ty)
+                    }
+                };
+                if !alive {
+                    storage.remove(key, *id);
+                }
+                alive
+            });
+        }
+    }
+}
+
+/// Reference to an index borrowed from the [`World`].
+///
+/// Dereferences to the index storage to perform lookups.
+///
+/// [`World`]: crate::world::World
+pub struct IndexRef<'a, I: Index> {
+    state: Res<'a, IndexState<I>>,
+}
+
+impl<'a, I> IndexRef<'a, I>
+where
+    I: Index,
+{
+    pub(crate) fn new(state: Res<'a, IndexState<I>>) -> Self {
+        IndexRef { state }
+    }
+
+    /// Returns key of the entity in the index.
+    /// Returns `None` if entity is not indexed.
+    #[inline(always)]
+    pub fn key_of(&self, id: EntityId) -> Option<&I::Key> {
+        self.state.keys.get(&id)
+    }
+
+    /// Returns number of indexed entities.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.state.keys.len()
+    }
+
+    /// Returns `true` if no entities are indexed.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.state.keys.is_empty()
+    }
+}
+
+impl<'a, I> Deref for IndexRef<'a, I>
+where
+    I: Index,
+{
+    type Target = I::Storage;
+
+    #[inline(always)]
+    fn deref(&self) -> &I::Storage {
+        &self.state.storage
+    }
+}
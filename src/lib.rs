@@ -575,6 +575,7 @@ pub mod dump;
 pub mod entity;
 pub mod epoch;
 pub mod executor;
+pub mod index;
 pub mod query;
 pub mod relation;
 pub mod resources;
@@ -675,6 +676,56 @@ impl fmt::Display for EntityError {
 #[cfg(feature = "std")]
 impl std::error::Error for EntityError {}
 
+/// Error that may be returned when fetching query from multiple entities at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GetManyError {
+    /// One of the entities is not found in the world.
+    NoSuchEntity,
+
+    /// One of the entities does not match requirements of the operation.
+    Mismatch,
+
+    /// Same entity is specified more than once.
+    Duplicate,
+}
+
+impl From<NoSuchEntity> for GetManyError {
+    #[inline(always)]
+    fn from(_: NoSuchEntity) -> Self {
+        GetManyError::NoSuchEntity
+    }
+}
+
+impl From<Mismatch> for GetManyError {
+    #[inline(always)]
+    fn from(_: Mismatch) -> Self {
+        GetManyError::Mismatch
+    }
+}
+
+impl From<EntityError> for GetManyError {
+    #[inline(always)]
+    fn from(err: EntityError) -> Self {
+        match err {
+            EntityError::NoSuchEntity => GetManyError::NoSuchEntity,
+            EntityError::Mismatch => GetManyError::Mismatch,
+        }
+    }
+}
+
+impl fmt::Display for GetManyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GetManyError::NoSuchEntity => fmt::Display::fmt(&NoSuchEntity, f),
+            GetManyError::Mismatch => fmt::Display::fmt(&Mismatch, f),
+            GetManyError::Duplicate => f.write_str("Same entity is specified more than once"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetManyError {}
+
 /// Specifies kind of access query performs for particular component.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Access {
@@ -696,7 +747,7 @@ pub mod private {
         ptr::NonNull,
     };
 
-    use crate::system::{IntoSystem, IsFunctionSystem};
+    use crate::system::{IntoPipeSystem, IntoSystem, IsFallibleFunctionSystem, IsFunctionSystem};
 
     pub use crate::system::FnArg;
 
@@ -705,6 +756,12 @@ pub mod private {
 
     #[inline(always)]
     pub fn is_fn_system<Args, F: IntoSystem<IsFunctionSystem<Args>>>(_: F) {}
+
+    #[inline(always)]
+    pub fn is_fallible_fn_system<Args, F: IntoSystem<IsFallibleFunctionSystem<Args>>>(_: F) {}
+
+    #[inline(always)]
+    pub fn is_fn_pipe_source<Args, F: IntoPipeSystem<(), IsFunctionSystem<Args>>>(_: F) {}
 }
 
 #[doc(hidden)]
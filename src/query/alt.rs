@@ -7,7 +7,7 @@ use core::{
 };
 
 use crate::{
-    archetype::{chunk_idx, Archetype},
+    archetype::{chunk_idx, Archetype, SnapshotWriter},
     component::ComponentInfo,
     epoch::EpochId,
     system::QueryArg,
@@ -26,6 +26,8 @@ pub struct RefMut<'a, T: ?Sized> {
     pub(super) chunk_epoch: &'a Cell<EpochId>,
     pub(super) archetype_epoch: &'a Cell<EpochId>,
     pub(super) epoch: EpochId,
+    pub(super) snapshots: Option<SnapshotWriter<'a>>,
+    pub(super) idx: u32,
 }
 
 impl<T> Deref for RefMut<'_, T> {
@@ -40,6 +42,15 @@ impl<T> Deref for RefMut<'_, T> {
 impl<T> DerefMut for RefMut<'_, T> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut T {
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(
+                    self.idx,
+                    NonNull::from(&mut *self.component).cast(),
+                    *self.entity_epoch,
+                );
+            }
+        }
         self.entity_epoch.bump_again(self.epoch);
         EpochId::bump_cell(&self.chunk_epoch, self.epoch);
         EpochId::bump_cell(&self.archetype_epoch, self.epoch);
@@ -54,6 +65,7 @@ pub struct FetchAlt<'a, T> {
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<Cell<EpochId>>,
     archetype_epoch: NonNull<Cell<EpochId>>,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a [T]>,
 }
 
@@ -71,6 +83,7 @@ where
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
             archetype_epoch: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -95,6 +108,8 @@ where
             chunk_epoch,
             archetype_epoch,
             epoch: self.epoch,
+            snapshots: self.snapshots,
+            idx,
         }
     }
 }
@@ -185,6 +200,7 @@ where
     ) -> FetchAlt<'a, T> {
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
         debug_assert_eq!(component.id(), type_id::<T>());
+        let snapshots = unsafe { SnapshotWriter::new(component) };
         let data = unsafe { component.data_mut() };
         debug_assert!(data.epoch.before(epoch));
 
@@ -194,6 +210,7 @@ where
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) }.cast(),
             archetype_epoch: NonNull::from(&mut data.epoch).cast(),
+            snapshots,
             marker: PhantomData,
         }
     }
@@ -1,40 +1,63 @@
-use super::{
-    boolean::{BooleanQuery, OrOp},
-    read::Read,
-    write::Write,
-    IntoQuery,
-};
+use super::{boolean::Or, AsQuery, DefaultQuery, IntoQuery};
 
 marker_type! {
     /// A query adaptor parameterized by a tuple of queries.
     /// Yields a tuple of items from each query wrapped in `Option`.
     /// Yields `None` for queries that do not match the entity.
     /// Skips if no queries match the entity.
+    ///
+    /// This is a shorthand for [`Or`] of the queries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component, query::AnyOf};
+    /// #[derive(Component)]
+    /// struct Sprite;
+    ///
+    /// #[derive(Component)]
+    /// struct Mesh;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn((Sprite,));
+    /// world.spawn((Mesh,));
+    /// world.spawn((Sprite, Mesh));
+    /// world.spawn(());
+    ///
+    /// let mut view = world.view::<AnyOf<(&Sprite, &Mesh)>>();
+    /// assert_eq!(view.iter().count(), 3);
+    /// assert_eq!(view.iter().filter(|(sprite, mesh)| sprite.is_some() && mesh.is_some()).count(), 1);
+    /// ```
     pub struct AnyOf<T>;
 }
 
 macro_rules! any_of {
     () => { /* Don't implement for empty tuple */ };
     ($($a:ident)+) => {
-        impl<$($a),+> IntoQuery for AnyOf<($(&$a,)+)>
+        impl<$($a),+> AsQuery for AnyOf<($($a,)+)>
         where
-            $($a: Sync + 'static,)+
+            $($a: AsQuery,)+
         {
-            type Query = BooleanQuery<($(Read<$a>,)+), OrOp>;
+            type Query = Or<($($a::Query,)+)>;
+        }
 
+        impl<$($a),+> IntoQuery for AnyOf<($($a,)+)>
+        where
+            $($a: DefaultQuery,)+
+        {
+            #[inline(always)]
             fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Read::<$a>,)*))
+                Or::from_tuple(($($a::default_query(),)+))
             }
         }
 
-        impl<$($a),+> IntoQuery for AnyOf<($(&mut $a,)+)>
+        impl<$($a),+> DefaultQuery for AnyOf<($($a,)+)>
         where
-            $($a: Send + 'static,)+
+            $($a: DefaultQuery,)+
         {
-            type Query = BooleanQuery<($(Write<$a>,)+), OrOp>;
-
-            fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Write::<$a>,)*))
+            #[inline(always)]
+            fn default_query() -> Self::Query {
+                Or::from_tuple(($($a::default_query(),)+))
             }
         }
     };
@@ -2,7 +2,7 @@ use core::{any::TypeId, marker::PhantomData, ops::ControlFlow};
 
 use crate::{
     archetype::Archetype, component::ComponentInfo, entity::EntityId, epoch::EpochId,
-    system::QueryArg,
+    system::QueryArg, world::World,
 };
 
 use super::{
@@ -287,6 +287,7 @@ macro_rules! impl_boolean {
             }
         }
 
+        #[allow(non_snake_case)]
         impl<Op $(, $a)+> QueryArg for BooleanQuery<($($a,)+), Op>
         where
             $($a: QueryArg,)+
@@ -299,6 +300,18 @@ macro_rules! impl_boolean {
                     op: PhantomData,
                 }
             }
+
+            #[inline(always)]
+            fn before(&mut self, world: &World) {
+                let ($($a,)+) = &mut self.tuple;
+                $($a.before(world);)+
+            }
+
+            #[inline(always)]
+            fn after(&mut self, world: &World) {
+                let ($($a,)+) = &mut self.tuple;
+                $($a.after(world);)+
+            }
         }
 
         #[allow(non_snake_case)]
@@ -312,6 +325,7 @@ macro_rules! impl_boolean {
             type Fetch<'a> = BooleanFetch<($($a::Fetch<'a>,)+), Op> where $($a: 'a,)+;
 
             const MUTABLE: bool = $($a::MUTABLE ||)+ false;
+            const FILTERS_ENTITIES: bool = $($a::FILTERS_ENTITIES ||)+ false;
 
             #[inline(always)]
             fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
@@ -358,7 +372,7 @@ macro_rules! impl_boolean {
                 let mut count = 0;
                 let mut mask = 0;
                 $(
-                    if unsafe { $a.visit_archetype_late(archetype) } {
+                    if $a.visit_archetype(archetype) && unsafe { $a.visit_archetype_late(archetype) } {
                         mask |= mi;
                     }
                     mi <<= 1;
@@ -2,7 +2,7 @@ use alloc::rc::Rc;
 use core::{any::TypeId, fmt::Debug, iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotWriter},
     component::{BorrowFn, BorrowFnMut, ComponentInfo},
     epoch::EpochId,
     query::{
@@ -17,16 +17,17 @@ use crate::{
 #[derive(Clone, Copy, Debug, Default)]
 pub struct BorrowAll<T>(pub T);
 
-struct FetchBorrowAllComponent<T: ?Sized> {
+struct FetchBorrowAllComponent<'a, T: ?Sized> {
     ptr: NonNull<u8>,
     size: usize,
     borrow_fn: BorrowFn<T>,
     borrow_mut_fn: Option<BorrowFnMut<T>>,
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotWriter<'a>>,
 }
 
-impl<T> Clone for FetchBorrowAllComponent<T>
+impl<T> Clone for FetchBorrowAllComponent<'_, T>
 where
     T: ?Sized,
 {
@@ -36,12 +37,45 @@ where
     }
 }
 
-impl<T> Copy for FetchBorrowAllComponent<T> where T: ?Sized {}
+impl<T> Copy for FetchBorrowAllComponent<'_, T> where T: ?Sized {}
+
+impl<'a, T> FetchBorrowAllComponent<'a, T>
+where
+    T: ?Sized,
+{
+    /// Borrows entity's component mutably, bumping its epoch.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be in bounds and mutable borrow function must exist.
+    /// Caller must have exclusive access to the entity's component.
+    #[inline(always)]
+    unsafe fn get_mut<'b>(
+        &self,
+        idx: u32,
+        epoch: EpochId,
+        marker: PhantomData<&'b mut T>,
+    ) -> &'b mut T {
+        let ptr =
+            unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize * self.size)) };
+        let entity_version = unsafe { &mut *self.entity_epochs.as_ptr().add(idx as usize) };
+
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(idx, ptr, *entity_version);
+            }
+        }
+        entity_version.bump(epoch);
+
+        // Safety: mutable borrow function exists. Checked in `Query::fetch`.
+        unsafe { (self.borrow_mut_fn.unwrap_unchecked())(ptr, marker) }
+    }
+}
 
 pub struct BorrowAllRead<'a, T: ?Sized> {
     idx: u32,
     comp_idx: usize,
-    components: Rc<[FetchBorrowAllComponent<T>]>,
+    components: Rc<[FetchBorrowAllComponent<'a, T>]>,
     marker: PhantomData<&'a T>,
 }
 
@@ -146,7 +180,7 @@ impl<'a, T> FusedIterator for BorrowAllRead<'a, T> where T: ?Sized {}
 
 /// [`Fetch`] for [`BorrowAll<&T>`].
 pub struct FetchBorrowAllRead<'a, T: ?Sized> {
-    components: Rc<[FetchBorrowAllComponent<T>]>,
+    components: Rc<[FetchBorrowAllComponent<'a, T>]>,
     marker: PhantomData<&'a T>,
 }
 
@@ -285,6 +319,7 @@ where
                         NonNull::new_unchecked(data.entity_epochs.as_mut_ptr())
                     },
                     chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
+                    snapshots: None,
                 }
             })
             .collect();
@@ -303,7 +338,7 @@ pub struct BorrowAllWrite<'a, T: ?Sized> {
     idx: u32,
     epoch: EpochId,
     comp_idx: usize,
-    components: Rc<[FetchBorrowAllComponent<T>]>,
+    components: Rc<[FetchBorrowAllComponent<'a, T>]>,
     marker: PhantomData<&'a mut T>,
 }
 
@@ -367,16 +402,7 @@ where
     #[inline(always)]
     fn next(&mut self) -> Option<&'a mut T> {
         let c = &self.components.get(self.comp_idx)?;
-        let r = unsafe {
-            let entity_version = &mut *c.entity_epochs.as_ptr().add(self.idx as usize);
-            entity_version.bump(self.epoch);
-
-            // Safety: mutable borrow function exists. Checked in `Query::fetch`.
-            (c.borrow_mut_fn.unwrap_unchecked())(
-                NonNull::new_unchecked(c.ptr.as_ptr().add(self.idx as usize * c.size)),
-                self.marker,
-            )
-        };
+        let r = unsafe { c.get_mut(self.idx, self.epoch, self.marker) };
         self.comp_idx += 1;
         Some(r)
     }
@@ -400,16 +426,7 @@ where
         for comp_idx in self.comp_idx..self.components.len() {
             let c = &self.components[comp_idx];
 
-            let r = unsafe {
-                let entity_version = &mut *c.entity_epochs.as_ptr().add(self.idx as usize);
-                entity_version.bump(self.epoch);
-
-                // Safety: mutable borrow function exists. Checked in `Query::fetch`.
-                (c.borrow_mut_fn.unwrap_unchecked())(
-                    NonNull::new_unchecked(c.ptr.as_ptr().add(self.idx as usize * c.size)),
-                    self.marker,
-                )
-            };
+            let r = unsafe { c.get_mut(self.idx, self.epoch, self.marker) };
             self.comp_idx += 1;
             accum = f(accum, r);
         }
@@ -431,7 +448,7 @@ impl<'a, T> FusedIterator for BorrowAllWrite<'a, T> where T: ?Sized {}
 
 /// [`Fetch`] for [`BorrowAll<&mut T>`].
 pub struct FetchBorrowAllWrite<'a, T: ?Sized> {
-    components: Rc<[FetchBorrowAllComponent<T>]>,
+    components: Rc<[FetchBorrowAllComponent<'a, T>]>,
     epoch: EpochId,
     marker: PhantomData<&'a mut T>,
 }
@@ -579,6 +596,8 @@ where
                 debug_assert_eq!(component.borrows()[idx].target(), type_id::<T>());
                 debug_assert!(component.borrows()[idx].borrow_mut::<T>().is_some());
 
+                let snapshots = unsafe { SnapshotWriter::new(component) };
+
                 let data = unsafe { component.data_mut() };
                 data.epoch.bump(epoch);
 
@@ -591,6 +610,7 @@ where
                         NonNull::new_unchecked(data.entity_epochs.as_mut_ptr())
                     },
                     chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
+                    snapshots,
                 }
             })
             .collect();
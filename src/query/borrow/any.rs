@@ -1,7 +1,7 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotWriter},
     component::{BorrowFn, BorrowFnMut, ComponentInfo},
     epoch::EpochId,
     query::{
@@ -174,6 +174,7 @@ pub struct FetchBorrowAnyWrite<'a, T: ?Sized> {
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
     epoch: EpochId,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a mut T>,
 }
 
@@ -192,6 +193,7 @@ where
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
             epoch: EpochId::start(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -204,15 +206,18 @@ where
 
     #[inline(always)]
     unsafe fn get_item(&mut self, idx: u32) -> &'a mut T {
+        let ptr =
+            unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize * self.size)) };
         let entity_version = unsafe { &mut *self.entity_epochs.as_ptr().add(idx as usize) };
-        entity_version.bump(self.epoch);
 
-        unsafe {
-            (self.borrow_fn)(
-                NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize * self.size)),
-                self.marker,
-            )
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(idx, ptr, *entity_version);
+            }
         }
+        entity_version.bump(self.epoch);
+
+        unsafe { (self.borrow_fn)(ptr, self.marker) }
     }
 }
 
@@ -322,6 +327,8 @@ where
         let component = unsafe { archetype.component(id).unwrap_unchecked() };
         debug_assert_eq!(component.borrows()[idx].target(), type_id::<T>());
 
+        let snapshots = unsafe { SnapshotWriter::new(component) };
+
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
 
@@ -332,6 +339,7 @@ where
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
             epoch,
+            snapshots,
             marker: PhantomData::<&'a mut T>,
         }
     }
@@ -1,7 +1,7 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotWriter},
     component::{BorrowFn, BorrowFnMut, ComponentInfo},
     epoch::EpochId,
     query::{
@@ -168,6 +168,7 @@ pub struct FetchBorrowOneWrite<'a, T: ?Sized> {
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
     epoch: EpochId,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a mut T>,
 }
 
@@ -186,6 +187,7 @@ where
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
             epoch: EpochId::start(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -198,15 +200,18 @@ where
 
     #[inline(always)]
     unsafe fn get_item(&mut self, idx: u32) -> &'a mut T {
+        let ptr =
+            unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize * self.size)) };
         let entity_version = unsafe { &mut *self.entity_epochs.as_ptr().add(idx as usize) };
-        entity_version.bump(self.epoch);
 
-        unsafe {
-            (self.borrow_fn)(
-                NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize * self.size)),
-                self.marker,
-            )
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(idx, ptr, *entity_version);
+            }
         }
+        entity_version.bump(self.epoch);
+
+        unsafe { (self.borrow_fn)(ptr, self.marker) }
     }
 }
 
@@ -283,6 +288,8 @@ where
 
         assert!(cb.borrow_mut::<T>().is_some());
 
+        let snapshots = unsafe { SnapshotWriter::new(component) };
+
         let data = unsafe { component.data_mut() };
 
         data.epoch.bump(epoch);
@@ -294,6 +301,7 @@ where
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
             epoch,
+            snapshots,
             marker: PhantomData::<&mut T>,
         }
     }
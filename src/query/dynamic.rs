@@ -0,0 +1,626 @@
+use alloc::rc::Rc;
+use core::{any::TypeId, fmt, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    archetype::{Archetype, SnapshotWriter},
+    component::ComponentInfo,
+    entity::EntityId,
+    epoch::EpochId,
+    system::QueryArg,
+    type_id, Access,
+};
+
+use super::{AsQuery, Fetch, IntoQuery, Query, SendQuery, WriteAlias};
+
+/// Kind of access [`DynamicQuery`] term performs on a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DynamicAccess {
+    /// Component is required and fetched for reading.
+    Read,
+
+    /// Component is required and fetched for writing.
+    Write,
+
+    /// Component is required but not fetched.
+    With,
+
+    /// Component must be absent.
+    Without,
+}
+
+/// Single term of the [`DynamicQuery`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DynamicTerm {
+    ty: TypeId,
+    access: DynamicAccess,
+}
+
+impl DynamicTerm {
+    /// Term that reads component `T`.
+    #[inline(always)]
+    pub fn read<T>() -> Self
+    where
+        T: Sync + 'static,
+    {
+        DynamicTerm {
+            ty: type_id::<T>(),
+            access: DynamicAccess::Read,
+        }
+    }
+
+    /// Term that writes component `T`.
+    #[inline(always)]
+    pub fn write<T>() -> Self
+    where
+        T: Send + 'static,
+    {
+        DynamicTerm {
+            ty: type_id::<T>(),
+            access: DynamicAccess::Write,
+        }
+    }
+
+    /// Term that requires component with specified type id.
+    #[inline(always)]
+    pub fn with(ty: TypeId) -> Self {
+        DynamicTerm {
+            ty,
+            access: DynamicAccess::With,
+        }
+    }
+
+    /// Term that requires absence of component with specified type id.
+    #[inline(always)]
+    pub fn without(ty: TypeId) -> Self {
+        DynamicTerm {
+            ty,
+            access: DynamicAccess::Without,
+        }
+    }
+
+    /// Term with arbitrary type id and access.
+    ///
+    /// # Safety
+    ///
+    /// Component type must be `Sync` for [`DynamicAccess::Read`]
+    /// and `Send` for [`DynamicAccess::Write`],
+    /// since [`DynamicQuery`] can be used from any thread.
+    #[inline(always)]
+    pub unsafe fn new_unchecked(ty: TypeId, access: DynamicAccess) -> Self {
+        DynamicTerm { ty, access }
+    }
+
+    /// Returns type id of the component.
+    #[inline(always)]
+    pub fn ty(&self) -> TypeId {
+        self.ty
+    }
+
+    /// Returns access kind of the term.
+    #[inline(always)]
+    pub fn access(&self) -> DynamicAccess {
+        self.access
+    }
+
+    #[inline(always)]
+    fn fetched(&self) -> Option<Access> {
+        match self.access {
+            DynamicAccess::Read => Some(Access::Read),
+            DynamicAccess::Write => Some(Access::Write),
+            DynamicAccess::With | DynamicAccess::Without => None,
+        }
+    }
+}
+
+/// Query composed at runtime from a list of [`DynamicTerm`]s.
+///
+/// Yields [`DynamicItem`] with type-erased components
+/// for each [`DynamicAccess::Read`] and [`DynamicAccess::Write`] term in order.
+///
+/// Terms are stored inline to keep query `Copy`,
+/// so query holds at most [`DynamicQuery::MAX_TERMS`] terms.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, component::Component, query::{DynamicQuery, DynamicTerm}};
+/// # use core::any::TypeId;
+/// #[derive(Component)]
+/// struct Pos(f32);
+///
+/// #[derive(Component)]
+/// struct Vel(f32);
+///
+/// #[derive(Component)]
+/// struct Frozen;
+///
+/// let mut world = World::new();
+/// world.spawn((Pos(0.0), Vel(1.0)));
+/// world.spawn((Pos(0.0), Vel(1.0), Frozen));
+///
+/// let query = DynamicQuery::new([
+///     DynamicTerm::write::<Pos>(),
+///     DynamicTerm::read::<Vel>(),
+///     DynamicTerm::without(TypeId::of::<Frozen>()),
+/// ]);
+///
+/// for mut item in world.view_with_mut(query) {
+///     let mut pos = item.next().unwrap();
+///     let vel = item.next().unwrap();
+///     assert_eq!(pos.info().name(), core::any::type_name::<Pos>());
+///     pos.downcast_mut::<Pos>().unwrap().0 += vel.downcast_ref::<Vel>().unwrap().0;
+/// }
+///
+/// let moved = world.view::<&Pos>().iter().filter(|pos| pos.0 == 1.0).count();
+/// assert_eq!(moved, 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct DynamicQuery {
+    terms: [DynamicTerm; DynamicQuery::MAX_TERMS],
+    len: u8,
+}
+
+impl fmt::Debug for DynamicQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynamicQuery").field(&self.terms()).finish()
+    }
+}
+
+impl DynamicQuery {
+    /// Maximum number of terms in the query.
+    pub const MAX_TERMS: usize = 16;
+
+    /// Creates query from a list of terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`DynamicQuery::MAX_TERMS`] terms.
+    pub fn new(terms: impl IntoIterator<Item = DynamicTerm>) -> Self {
+        let mut query = DynamicQuery {
+            terms: [DynamicTerm::with(type_id::<()>()); DynamicQuery::MAX_TERMS],
+            len: 0,
+        };
+        for term in terms {
+            assert!(
+                usize::from(query.len) < DynamicQuery::MAX_TERMS,
+                "Too many terms in dynamic query"
+            );
+            query.terms[usize::from(query.len)] = term;
+            query.len += 1;
+        }
+        query
+    }
+
+    /// Returns terms of the query.
+    #[inline(always)]
+    pub fn terms(&self) -> &[DynamicTerm] {
+        &self.terms[..usize::from(self.len)]
+    }
+}
+
+/// Type-erased component fetched by [`DynamicQuery`].
+pub struct DynamicComponent<'a> {
+    ptr: NonNull<u8>,
+    info: &'a ComponentInfo,
+    access: Access,
+    marker: PhantomData<&'a mut u8>,
+}
+
+impl<'a> DynamicComponent<'a> {
+    /// Returns pointer to the component value.
+    ///
+    /// Pointer may be used for writing only if [`access`](Self::access) is [`Access::Write`].
+    #[inline(always)]
+    pub fn ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+
+    /// Returns information about component type.
+    #[inline(always)]
+    pub fn info(&self) -> &'a ComponentInfo {
+        self.info
+    }
+
+    /// Returns access granted to the component.
+    #[inline(always)]
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    /// Returns reference to the component if it is of type `T`.
+    #[inline(always)]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        if self.info.id() != type_id::<T>() {
+            return None;
+        }
+        Some(unsafe { self.ptr.cast::<T>().as_ref() })
+    }
+
+    /// Returns mutable reference to the component if it is of type `T`
+    /// and fetched for writing.
+    #[inline(always)]
+    pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        if self.info.id() != type_id::<T>() || self.access != Access::Write {
+            return None;
+        }
+        Some(unsafe { self.ptr.cast::<T>().as_mut() })
+    }
+}
+
+/// Component fetched by [`DynamicFetch`], resolved once per archetype.
+struct DynamicColumn<'a> {
+    ptr: NonNull<u8>,
+    size: usize,
+    info: &'a ComponentInfo,
+    access: Access,
+    entity_epochs: NonNull<EpochId>,
+    chunk_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotWriter<'a>>,
+}
+
+/// Item type of [`DynamicQuery`].
+///
+/// Iterator over components fetched by the query
+/// in order of [`DynamicAccess::Read`] and [`DynamicAccess::Write`] terms.
+pub struct DynamicItem<'a> {
+    columns: Rc<[DynamicColumn<'a>]>,
+    next: usize,
+    idx: u32,
+}
+
+impl<'a> Iterator for DynamicItem<'a> {
+    type Item = DynamicComponent<'a>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<DynamicComponent<'a>> {
+        let column = self.columns.get(self.next)?;
+        self.next += 1;
+
+        Some(DynamicComponent {
+            ptr: unsafe {
+                NonNull::new_unchecked(column.ptr.as_ptr().add(column.size * self.idx as usize))
+            },
+            info: column.info,
+            access: column.access,
+            marker: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.columns.len() - self.next;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for DynamicItem<'_> {}
+
+/// [`Fetch`] type for the [`DynamicQuery`].
+pub struct DynamicFetch<'a> {
+    columns: Rc<[DynamicColumn<'a>]>,
+    epoch: EpochId,
+}
+
+unsafe impl<'a> Fetch<'a> for DynamicFetch<'a> {
+    type Item = DynamicItem<'a>;
+
+    #[inline(always)]
+    fn dangling() -> Self {
+        DynamicFetch {
+            columns: Rc::new([]),
+            epoch: EpochId::start(),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn touch_chunk(&mut self, chunk_idx: u32) {
+        for column in self.columns.iter() {
+            if column.access == Access::Write {
+                let chunk_epoch =
+                    unsafe { &mut *column.chunk_epochs.as_ptr().add(chunk_idx as usize) };
+                chunk_epoch.bump(self.epoch);
+            }
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn get_item(&mut self, idx: u32) -> DynamicItem<'a> {
+        for column in self.columns.iter() {
+            if column.access == Access::Write {
+                let entity_epoch = unsafe { &mut *column.entity_epochs.as_ptr().add(idx as usize) };
+
+                if let Some(snapshots) = &column.snapshots {
+                    unsafe {
+                        let ptr = NonNull::new_unchecked(
+                            column.ptr.as_ptr().add(column.size * idx as usize),
+                        );
+                        snapshots.record(idx, ptr, *entity_epoch);
+                    }
+                }
+                entity_epoch.bump(self.epoch);
+            }
+        }
+
+        DynamicItem {
+            columns: self.columns.clone(),
+            next: 0,
+            idx,
+        }
+    }
+}
+
+impl AsQuery for DynamicQuery {
+    type Query = Self;
+}
+
+impl IntoQuery for DynamicQuery {
+    #[inline(always)]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+unsafe impl Query for DynamicQuery {
+    type Item<'a> = DynamicItem<'a>;
+    type Fetch<'a> = DynamicFetch<'a>;
+
+    const MUTABLE: bool = true;
+
+    #[inline(always)]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        let mut result = None;
+        for term in self.terms() {
+            if term.ty != comp.id() {
+                continue;
+            }
+            result = match (result, term.fetched()) {
+                (None, one) | (one, None) => one,
+                (Some(Access::Read), Some(Access::Read)) => Some(Access::Read),
+                _ => return Err(WriteAlias),
+            };
+        }
+        Ok(result)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.terms().iter().all(|term| match term.access {
+            DynamicAccess::Without => !archetype.has_component(term.ty),
+            _ => archetype.has_component(term.ty),
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn access_archetype(&self, _archetype: &Archetype, mut f: impl FnMut(TypeId, Access)) {
+        for term in self.terms() {
+            if let Some(access) = term.fetched() {
+                f(term.ty, access);
+            }
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'a>(
+        &self,
+        _arch_idx: u32,
+        archetype: &'a Archetype,
+        epoch: EpochId,
+    ) -> DynamicFetch<'a> {
+        let columns = self
+            .terms()
+            .iter()
+            .filter_map(|term| {
+                let access = term.fetched()?;
+                let component = unsafe { archetype.component(term.ty).unwrap_unchecked() };
+
+                let size = component.layout().size();
+
+                let column = match access {
+                    Access::Read => {
+                        let data = unsafe { component.data() };
+                        DynamicColumn {
+                            ptr: data.ptr,
+                            size,
+                            info: component,
+                            access,
+                            entity_epochs: NonNull::dangling(),
+                            chunk_epochs: NonNull::dangling(),
+                            snapshots: None,
+                        }
+                    }
+                    Access::Write => {
+                        let snapshots = unsafe { SnapshotWriter::new(component) };
+                        let data = unsafe { component.data_mut() };
+                        data.epoch.bump(epoch);
+
+                        DynamicColumn {
+                            ptr: data.ptr,
+                            size,
+                            info: component,
+                            access,
+                            entity_epochs: unsafe {
+                                NonNull::new_unchecked(data.entity_epochs.as_mut_ptr())
+                            },
+                            chunk_epochs: unsafe {
+                                NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr())
+                            },
+                            snapshots,
+                        }
+                    }
+                };
+                Some(column)
+            })
+            .collect();
+
+        DynamicFetch { columns, epoch }
+    }
+
+    #[inline(always)]
+    fn reserved_entity_item<'a>(&self, id: EntityId, idx: u32) -> Option<DynamicItem<'a>> {
+        let _ = id;
+        if self
+            .terms()
+            .iter()
+            .all(|term| term.access == DynamicAccess::Without)
+        {
+            Some(DynamicItem {
+                columns: Rc::new([]),
+                next: 0,
+                idx,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Terms of the query can be constructed only for thread-safe components
+/// unless [`DynamicTerm::new_unchecked`] is used.
+unsafe impl SendQuery for DynamicQuery {}
+
+/// Provides [`DynamicQuery`] for [`DynamicArg`].
+pub trait DynamicQuerySource: 'static {
+    /// Returns query to use.
+    /// Called once when function-system argument state is created.
+    fn query() -> DynamicQuery;
+}
+
+/// [`DynamicQuery`] with terms provided by `S`.
+///
+/// Unlike [`DynamicQuery`] it implements [`QueryArg`],
+/// so it can be used in views of function-systems.
+/// Scheduler sees components accessed by the terms.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, component::Component, scheduler::Scheduler, query::{DynamicQuery, DynamicQuerySource, DynamicTerm, DynamicView}};
+/// #[derive(Component)]
+/// struct Pos(f32);
+///
+/// struct MovePos;
+///
+/// impl DynamicQuerySource for MovePos {
+///     fn query() -> DynamicQuery {
+///         DynamicQuery::new([DynamicTerm::write::<Pos>()])
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.spawn((Pos(0.0),));
+///
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system(|view: DynamicView<MovePos>| {
+///     for mut item in view {
+///         item.next().unwrap().downcast_mut::<Pos>().unwrap().0 += 1.0;
+///     }
+/// });
+/// scheduler.run_sequential(&mut world);
+///
+/// assert_eq!(world.view::<&Pos>().iter().next().unwrap().0, 1.0);
+/// ```
+pub struct DynamicArg<S> {
+    query: DynamicQuery,
+    marker: PhantomData<fn() -> S>,
+}
+
+/// [`View`] over [`DynamicQuery`] provided by `S`, usable as function-system argument.
+///
+/// [`View`]: crate::view::View
+pub type DynamicView<'a, S, F = ()> = crate::view::View<'a, DynamicArg<S>, F>;
+
+impl<S> Clone for DynamicArg<S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for DynamicArg<S> {}
+
+impl<S> fmt::Debug for DynamicArg<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynamicArg").field(&self.query).finish()
+    }
+}
+
+impl<S> DynamicArg<S> {
+    /// Returns wrapped query.
+    #[inline(always)]
+    pub fn query(&self) -> DynamicQuery {
+        self.query
+    }
+}
+
+impl<S> AsQuery for DynamicArg<S>
+where
+    S: 'static,
+{
+    type Query = Self;
+}
+
+impl<S> IntoQuery for DynamicArg<S>
+where
+    S: 'static,
+{
+    #[inline(always)]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+impl<S> QueryArg for DynamicArg<S>
+where
+    S: DynamicQuerySource,
+{
+    #[inline(always)]
+    fn new() -> Self {
+        DynamicArg {
+            query: S::query(),
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<S> Query for DynamicArg<S>
+where
+    S: 'static,
+{
+    type Item<'a> = DynamicItem<'a>;
+    type Fetch<'a> = DynamicFetch<'a>;
+
+    const MUTABLE: bool = true;
+
+    #[inline(always)]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        self.query.component_access(comp)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.query.visit_archetype(archetype)
+    }
+
+    #[inline(always)]
+    unsafe fn access_archetype(&self, archetype: &Archetype, f: impl FnMut(TypeId, Access)) {
+        unsafe { self.query.access_archetype(archetype, f) }
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'a>(
+        &self,
+        arch_idx: u32,
+        archetype: &'a Archetype,
+        epoch: EpochId,
+    ) -> DynamicFetch<'a> {
+        unsafe { self.query.fetch(arch_idx, archetype, epoch) }
+    }
+
+    #[inline(always)]
+    fn reserved_entity_item<'a>(&self, id: EntityId, idx: u32) -> Option<DynamicItem<'a>> {
+        self.query.reserved_entity_item(id, idx)
+    }
+}
+
+/// Same as for [`DynamicQuery`].
+unsafe impl<S> SendQuery for DynamicArg<S> where S: 'static {}
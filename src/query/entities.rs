@@ -70,6 +70,17 @@ impl DefaultQuery for Entities {
     }
 }
 
+impl AsQuery for EntityLoc<'_> {
+    type Query = Entities;
+}
+
+impl DefaultQuery for EntityLoc<'_> {
+    #[inline(always)]
+    fn default_query() -> Entities {
+        Entities
+    }
+}
+
 impl QueryArg for Entities {
     #[inline(always)]
     fn new() -> Self {
@@ -23,9 +23,38 @@
 //! a specific entity.
 //! [`View`]s can also be used as function-system arguments.
 //!
+//! Query that yields a struct with named fields can be derived with `#[derive(Query)]`.
+//! Each field type must be an item type of some query, like `&'a T`, `&'a mut T`,
+//! `Option<&'a T>` or [`EntityLoc<'a>`].
+//! Derive generates query type named after the struct with `Query` suffix.
+//!
+//! ```
+//! # use edict::{world::World, component::Component, entity::EntityLoc, query::Query};
+//! #[derive(Component)]
+//! struct Pos(f32);
+//!
+//! #[derive(Component)]
+//! struct Vel(f32);
+//!
+//! #[derive(Query)]
+//! struct Mover<'a> {
+//!     pos: &'a mut Pos,
+//!     vel: &'a Vel,
+//!     id: EntityLoc<'a>,
+//! }
+//!
+//! let mut world = World::new();
+//! world.spawn((Pos(0.0), Vel(1.0)));
+//!
+//! for Mover { pos, vel, .. } in world.view_mut::<Mover>() {
+//!     pos.0 += vel.0;
+//! }
+//! ```
+//!
 //! [`World`]: crate::world::World
 //! [`View`]: crate::view::View
 //! [`Entity`]: crate::entity::Entity
+//! [`EntityLoc<'a>`]: crate::entity::EntityLoc
 //!
 
 use core::any::TypeId;
@@ -36,7 +65,7 @@ use crate::{
 
 pub use self::{
     alt::{Alt, FetchAlt},
-    // any_of::AnyOf,
+    any_of::AnyOf,
     boolean::{
         And, And2, And3, And4, And5, And6, And7, And8, BooleanFetch, BooleanFetchOp, BooleanQuery,
         Or, Or2, Or3, Or4, Or5, Or6, Or7, Or8, Xor, Xor2, Xor3, Xor4, Xor5, Xor6, Xor7, Xor8,
@@ -46,6 +75,10 @@ pub use self::{
         FetchBorrowAnyWrite, FetchBorrowOneRead, FetchBorrowOneWrite,
     },
     copied::{Cpy, FetchCpy},
+    dynamic::{
+        DynamicAccess, DynamicArg, DynamicComponent, DynamicFetch, DynamicItem, DynamicQuery,
+        DynamicQuerySource, DynamicTerm, DynamicView,
+    },
     entities::{Entities, EntitiesFetch},
     fetch::{BatchFetch, Fetch, UnitFetch, VerifyFetch},
     filter::{FilteredFetch, Not, With, Without},
@@ -58,11 +91,14 @@ pub use self::{
     write::{FetchWrite, Write},
 };
 
+pub use edict_proc::Query;
+
 mod alt;
-// mod any_of;
+mod any_of;
 mod boolean;
 mod borrow;
 mod copied;
+mod dynamic;
 mod entities;
 mod fetch;
 mod filter;
@@ -1,7 +1,7 @@
 use core::{any::TypeId, cell::Cell, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::{chunk_idx, Archetype},
+    archetype::{chunk_idx, Archetype, SnapshotWriter},
     component::ComponentInfo,
     epoch::EpochId,
     query::{
@@ -23,6 +23,7 @@ pub struct ModifiedFetchAlt<'a, T> {
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<Cell<EpochId>>,
     archetype_epoch: NonNull<Cell<EpochId>>,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a mut [T]>,
 }
 
@@ -41,6 +42,7 @@ where
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
             archetype_epoch: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -71,6 +73,8 @@ where
             chunk_epoch,
             archetype_epoch,
             epoch: self.epoch,
+            snapshots: self.snapshots,
+            idx,
         }
     }
 }
@@ -158,6 +162,8 @@ where
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
         debug_assert_eq!(component.id(), type_id::<T>());
 
+        let snapshots = unsafe { SnapshotWriter::new(component) };
+
         let data = unsafe { component.data_mut() };
 
         debug_assert!(data.epoch.after(self.after_epoch));
@@ -170,6 +176,7 @@ where
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()).cast() },
             archetype_epoch: NonNull::from(&mut data.epoch).cast(),
+            snapshots,
             marker: PhantomData,
         }
     }
@@ -273,6 +280,7 @@ where
         match archetype.component(type_id::<T>()) {
             None => None,
             Some(component) => {
+                let snapshots = unsafe { SnapshotWriter::new(component) };
                 let data = unsafe { component.data_mut() };
 
                 debug_assert!(data.epoch.after(self.after_epoch));
@@ -290,6 +298,7 @@ where
                         NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()).cast()
                     },
                     archetype_epoch: NonNull::from(&mut data.epoch).cast(),
+                    snapshots,
                     marker: PhantomData,
                 })
             }
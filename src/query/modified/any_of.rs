@@ -1,53 +1,29 @@
-use crate::query::{
-    any_of::AnyOf, boolean::BooleanQuery, boolean::OrOp, read::Read, write::Write, IntoQuery,
-};
+use crate::query::{any_of::AnyOf, boolean::Or, AsQuery, DefaultQuery, IntoQuery, Query};
 
 use super::Modified;
 
 macro_rules! any_of {
     () => { /* Don't implement for empty tuple */ };
     ($($a:ident)+) => {
-        impl<$($a),+> IntoQuery for Modified<AnyOf<($(&$a,)+)>>
+        impl<$($a),+> AsQuery for Modified<AnyOf<($($a,)+)>>
         where
-            $($a: Sync + 'static,)+
+            $($a: AsQuery, Modified<$a::Query>: Query,)+
         {
-            type Query = BooleanQuery<($(Modified<Read<$a>>,)+), OrOp>;
-
-            fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Modified::<Read<$a>>::new(self.after_epoch),)*))
-            }
-        }
-
-        impl<$($a),+> IntoQuery for Modified<AnyOf<($(Read<$a>,)+)>>
-        where
-            $($a: Sync + 'static,)+
-        {
-            type Query = BooleanQuery<($(Modified<Read<$a>>,)+), OrOp>;
-
-            fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Modified::<Read<$a>>::new(self.after_epoch),)*))
-            }
+            type Query = Or<($(Modified<$a::Query>,)+)>;
         }
 
-        impl<$($a),+> IntoQuery for Modified<AnyOf<($(&mut $a,)+)>>
+        impl<$($a),+> IntoQuery for Modified<AnyOf<($($a,)+)>>
         where
-            $($a: Send + 'static,)+
+            $($a: DefaultQuery, Modified<$a::Query>: Query,)+
         {
-            type Query = BooleanQuery<($(Modified<Write<$a>>,)+), OrOp>;
-
-            fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Modified::<Write<$a>>::new(self.after_epoch),)*))
-            }
-        }
-
-        impl<$($a),+> IntoQuery for Modified<AnyOf<($(Write<$a>,)+)>>
-        where
-            $($a: Send + 'static,)+
-        {
-            type Query = BooleanQuery<($(Modified<Write<$a>>,)+), OrOp>;
-
+            #[inline(always)]
             fn into_query(self) -> Self::Query {
-                BooleanQuery::from_tuple(($(Modified::<Write<$a>>::new(self.after_epoch),)*))
+                Or::from_tuple(($(
+                    Modified {
+                        after_epoch: self.after_epoch,
+                        query: $a::default_query(),
+                    },
+                )+))
             }
         }
     };
@@ -1,7 +1,7 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotReader},
     component::ComponentInfo,
     epoch::EpochId,
     query::{
@@ -21,6 +21,7 @@ pub struct ModifiedFetchCopied<'a, T> {
     ptr: NonNull<T>,
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotReader>,
     marker: PhantomData<&'a [T]>,
 }
 
@@ -37,6 +38,7 @@ where
             ptr: NonNull::dangling(),
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -49,7 +51,11 @@ where
 
     #[inline(always)]
     unsafe fn visit_item(&mut self, idx: u32) -> bool {
-        let epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        let mut epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        if let Some(snapshots) = &self.snapshots {
+            let value = unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize)) };
+            epoch = unsafe { snapshots.epoch(idx, value.cast(), epoch) };
+        }
         epoch.after(self.after_epoch)
     }
 
@@ -139,6 +145,7 @@ where
         _epoch: EpochId,
     ) -> ModifiedFetchCopied<'a, T> {
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
+        let snapshots = unsafe { SnapshotReader::new(component) };
         let data = unsafe { component.data() };
 
         debug_assert!(data.epoch.after(self.after_epoch));
@@ -152,6 +159,7 @@ where
             chunk_epochs: unsafe {
                 NonNull::new_unchecked(data.chunk_epochs.as_ptr() as *mut EpochId)
             },
+            snapshots,
             marker: PhantomData,
         }
     }
@@ -256,6 +264,7 @@ where
         match archetype.component(type_id::<T>()) {
             None => None,
             Some(component) => {
+                let snapshots = unsafe { SnapshotReader::new(component) };
                 let data = unsafe { component.data() };
 
                 debug_assert!(data.epoch.after(self.after_epoch));
@@ -269,6 +278,7 @@ where
                     chunk_epochs: unsafe {
                         NonNull::new_unchecked(data.chunk_epochs.as_ptr() as *mut EpochId)
                     },
+                    snapshots,
                     marker: PhantomData,
                 })
             }
@@ -1,5 +1,5 @@
 mod alt;
-// mod any_of;
+mod any_of;
 mod copied;
 mod read;
 mod with;
@@ -18,6 +18,67 @@ pub use self::{
 /// or [`Modified<Alt<T>>`].
 ///
 /// This is tracking query that uses epoch lower bound to filter out entities with unmodified components.
+///
+/// When used in function-system's [`View`] argument,
+/// the query remembers the epoch at which the system finished its last run
+/// and yields only components modified since then.
+/// On the first run all components are considered modified.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, component::Component, query::Modified, resources::ResMut, scheduler::Scheduler, view::View};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.insert_resource(0usize);
+/// let e = world.spawn((Health(10),)).id();
+///
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system(|view: View<Modified<&Health>>, mut changed: ResMut<usize>| {
+///     *changed = view.iter().count();
+/// });
+///
+/// scheduler.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<usize>(), 1);
+///
+/// scheduler.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<usize>(), 0);
+///
+/// world.get::<&mut Health>(e).unwrap().0 = 5;
+/// scheduler.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<usize>(), 1);
+/// ```
+///
+/// # Compare-on-write
+///
+/// By default any mutable access marks the component as modified,
+/// even if the value is left unchanged.
+/// Components with compare-on-write enabled are snapshotted before write
+/// and writes that leave the value equal to the snapshot are not reported.
+/// Enable it with `#[edict(compare_on_write)]` attribute on `#[derive(Component)]`
+/// or with [`ComponentInfoRef::compare_on_write`] when registering the component.
+///
+/// ```
+/// # use edict::{world::World, component::Component, query::{Modified, Read}};
+/// #[derive(Clone, PartialEq, Component)]
+/// #[edict(compare_on_write)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// let e = world.spawn((Health(10),)).id();
+/// let after = world.epoch();
+///
+/// world.get::<&mut Health>(e).unwrap().0 = 10;
+/// assert_eq!(world.view_with(Modified::<Read<Health>>::new(after)).iter().count(), 0);
+///
+/// world.get::<&mut Health>(e).unwrap().0 = 5;
+/// assert_eq!(world.view_with(Modified::<Read<Health>>::new(after)).iter().count(), 1);
+/// ```
+///
+/// [`View`]: crate::view::View
+/// [`ComponentInfoRef::compare_on_write`]: crate::component::ComponentInfoRef::compare_on_write
 #[derive(Clone, Copy, Debug)]
 pub struct Modified<T> {
     after_epoch: EpochId,
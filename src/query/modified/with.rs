@@ -1,7 +1,7 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotReader},
     component::ComponentInfo,
     epoch::EpochId,
     query::{
@@ -18,8 +18,10 @@ use super::Modified;
 /// [`Fetch`] type for the [`Modified<&T>`] query.
 pub struct ModifiedFetchWith<'a, T> {
     after_epoch: EpochId,
+    ptr: NonNull<T>,
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotReader>,
     marker: PhantomData<&'a [T]>,
 }
 
@@ -33,8 +35,10 @@ where
     fn dangling() -> Self {
         ModifiedFetchWith {
             after_epoch: EpochId::start(),
+            ptr: NonNull::dangling(),
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -47,7 +51,11 @@ where
 
     #[inline(always)]
     unsafe fn visit_item(&mut self, idx: u32) -> bool {
-        let epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        let mut epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        if let Some(snapshots) = &self.snapshots {
+            let value = unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(idx as usize)) };
+            epoch = unsafe { snapshots.epoch(idx, value.cast(), epoch) };
+        }
         epoch.after(self.after_epoch)
     }
 
@@ -135,18 +143,21 @@ where
         _epoch: EpochId,
     ) -> ModifiedFetchWith<'a, T> {
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
+        let snapshots = unsafe { SnapshotReader::new(component) };
         let data = unsafe { component.data() };
 
         debug_assert!(data.epoch.after(self.after_epoch));
 
         ModifiedFetchWith {
             after_epoch: self.after_epoch,
+            ptr: data.ptr.cast(),
             entity_epochs: unsafe {
                 NonNull::new_unchecked(data.entity_epochs.as_ptr() as *mut EpochId)
             },
             chunk_epochs: unsafe {
                 NonNull::new_unchecked(data.chunk_epochs.as_ptr() as *mut EpochId)
             },
+            snapshots,
             marker: PhantomData,
         }
     }
@@ -1,7 +1,7 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype,
+    archetype::{Archetype, SnapshotWriter},
     component::ComponentInfo,
     epoch::EpochId,
     query::{
@@ -22,6 +22,7 @@ pub struct ModifiedFetchWrite<'a, T> {
     ptr: NonNull<T>,
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a mut [T]>,
 }
 
@@ -39,6 +40,7 @@ where
             ptr: NonNull::dangling(),
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -63,10 +65,17 @@ where
 
     #[inline(always)]
     unsafe fn get_item(&mut self, idx: u32) -> &'a mut T {
+        let ptr = unsafe { self.ptr.as_ptr().add(idx as usize) };
         let entity_epoch = unsafe { &mut *self.entity_epochs.as_ptr().add(idx as usize) };
+
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(idx, NonNull::new_unchecked(ptr).cast(), *entity_epoch);
+            }
+        }
         entity_epoch.bump(self.epoch);
 
-        unsafe { &mut *self.ptr.as_ptr().add(idx as usize) }
+        unsafe { &mut *ptr }
     }
 }
 
@@ -157,6 +166,7 @@ where
         epoch: EpochId,
     ) -> ModifiedFetchWrite<'a, T> {
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
+        let snapshots = unsafe { SnapshotWriter::new(component) };
         let data = unsafe { component.data_mut() };
 
         debug_assert!(data.epoch.after(self.after_epoch));
@@ -168,6 +178,7 @@ where
             ptr: data.ptr.cast(),
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
+            snapshots,
             marker: PhantomData,
         }
     }
@@ -199,6 +210,24 @@ where
     }
 }
 
+impl<T> QueryArg for Modified<OptionQuery<Write<T>>>
+where
+    T: Send + 'static,
+{
+    #[inline(always)]
+    fn new() -> Self {
+        Modified {
+            after_epoch: EpochId::start(),
+            query: OptionQuery(Write),
+        }
+    }
+
+    #[inline(always)]
+    fn after(&mut self, world: &World) {
+        self.after_epoch = world.epoch();
+    }
+}
+
 unsafe impl<T> Query for Modified<OptionQuery<Write<T>>>
 where
     T: 'static,
@@ -253,6 +282,7 @@ where
         match archetype.component(type_id::<T>()) {
             None => None,
             Some(component) => {
+                let snapshots = unsafe { SnapshotWriter::new(component) };
                 let data = unsafe { component.data_mut() };
 
                 debug_assert!(data.epoch.after(self.after_epoch));
@@ -265,6 +295,7 @@ where
                         NonNull::new_unchecked(data.entity_epochs.as_mut_ptr())
                     },
                     chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
+                    snapshots,
                     marker: PhantomData,
                 })
             }
@@ -1,7 +1,11 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype, component::ComponentInfo, epoch::EpochId, system::QueryArg, type_id,
+    archetype::{Archetype, SnapshotReader},
+    component::ComponentInfo,
+    epoch::EpochId,
+    system::QueryArg,
+    type_id,
 };
 
 use super::{
@@ -13,7 +17,10 @@ mod read;
 
 /// Fetch for [`EpochOf`] epochs.
 pub struct FetchEpoch<'a> {
+    ptr: NonNull<u8>,
+    size: usize,
     entity_epochs: NonNull<EpochId>,
+    snapshots: Option<SnapshotReader>,
     marker: PhantomData<&'a [EpochId]>,
 }
 
@@ -23,14 +30,24 @@ unsafe impl<'a> Fetch<'a> for FetchEpoch<'a> {
     #[inline(always)]
     fn dangling() -> Self {
         FetchEpoch {
+            ptr: NonNull::dangling(),
+            size: 0,
             entity_epochs: NonNull::dangling(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
 
     #[inline(always)]
     unsafe fn get_item(&mut self, idx: u32) -> EpochId {
-        unsafe { *self.entity_epochs.as_ptr().add(idx as usize) }
+        let epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        match &self.snapshots {
+            None => epoch,
+            Some(snapshots) => unsafe {
+                let value = NonNull::new_unchecked(self.ptr.as_ptr().add(self.size * idx as usize));
+                snapshots.epoch(idx, value, epoch)
+            },
+        }
     }
 }
 
@@ -112,12 +129,16 @@ where
         _epoch: EpochId,
     ) -> FetchEpoch<'a> {
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
+        let snapshots = unsafe { SnapshotReader::new(component) };
         let data = unsafe { component.data() };
 
         FetchEpoch {
+            ptr: data.ptr,
+            size: component.layout().size(),
             entity_epochs: unsafe {
                 NonNull::new_unchecked(data.entity_epochs.as_ptr() as *mut EpochId)
             },
+            snapshots,
             marker: PhantomData,
         }
     }
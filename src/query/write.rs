@@ -1,7 +1,11 @@
 use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
 
 use crate::{
-    archetype::Archetype, component::ComponentInfo, epoch::EpochId, system::QueryArg, type_id,
+    archetype::{Archetype, SnapshotWriter},
+    component::ComponentInfo,
+    epoch::EpochId,
+    system::QueryArg,
+    type_id,
 };
 
 use super::{
@@ -14,6 +18,7 @@ pub struct FetchWrite<'a, T> {
     entity_epochs: NonNull<EpochId>,
     chunk_epochs: NonNull<EpochId>,
     epoch: EpochId,
+    snapshots: Option<SnapshotWriter<'a>>,
     marker: PhantomData<&'a mut [T]>,
 }
 
@@ -32,6 +37,7 @@ where
             entity_epochs: NonNull::dangling(),
             chunk_epochs: NonNull::dangling(),
             epoch: EpochId::start(),
+            snapshots: None,
             marker: PhantomData,
         }
     }
@@ -44,10 +50,17 @@ where
 
     #[inline(always)]
     unsafe fn get_item(&mut self, idx: u32) -> &'a mut T {
+        let ptr = unsafe { self.ptr.as_ptr().add(idx as usize) };
         let entity_epoch = unsafe { &mut *self.entity_epochs.as_ptr().add(idx as usize) };
+
+        if let Some(snapshots) = &self.snapshots {
+            unsafe {
+                snapshots.record(idx, NonNull::new_unchecked(ptr).cast(), *entity_epoch);
+            }
+        }
         entity_epoch.bump(self.epoch);
 
-        unsafe { &mut *self.ptr.as_ptr().add(idx as usize) }
+        unsafe { &mut *ptr }
     }
 }
 
@@ -165,6 +178,8 @@ where
         let component = unsafe { archetype.component(type_id::<T>()).unwrap_unchecked() };
         debug_assert_eq!(component.id(), type_id::<T>());
 
+        let snapshots = unsafe { SnapshotWriter::new(component) };
+
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
 
@@ -173,6 +188,7 @@ where
             entity_epochs: unsafe { NonNull::new_unchecked(data.entity_epochs.as_mut_ptr()) },
             chunk_epochs: unsafe { NonNull::new_unchecked(data.chunk_epochs.as_mut_ptr()) },
             epoch,
+            snapshots,
             marker: PhantomData,
         }
     }
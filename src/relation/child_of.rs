@@ -1,3 +1,10 @@
+use crate::{
+    bundle::{DynamicBundle, DynamicComponentBundle},
+    component::Component,
+    entity::{EntityId, EntityRef},
+    world::World,
+};
+
 use super::{ExclusiveRelation, Relation};
 
 /// Child -> Parent relation.
@@ -13,3 +20,99 @@ impl Relation for ChildOf {
 }
 
 impl ExclusiveRelation for ChildOf {}
+
+/// Spawns children of an entity.
+/// Each spawned entity gets [`ChildOf`] relation to the parent.
+///
+/// Provided to the closure passed to [`EntityRef::with_children`].
+pub struct ChildSpawner<'a> {
+    parent: EntityId,
+    world: &'a mut World,
+}
+
+impl<'a> ChildSpawner<'a> {
+    #[inline(always)]
+    pub(crate) fn new(parent: EntityId, world: &'a mut World) -> Self {
+        ChildSpawner { parent, world }
+    }
+
+    /// Returns id of the parent entity.
+    #[inline(always)]
+    pub fn parent(&self) -> EntityId {
+        self.parent
+    }
+
+    /// Spawns a child entity with provided component.
+    ///
+    /// Returns `None` if parent entity was despawned by a hook
+    /// before child is attached to it,
+    /// or if child entity was despawned by a hook
+    /// triggered when relation was inserted.
+    #[inline(always)]
+    pub fn spawn_one<T>(&mut self, component: T) -> Option<EntityRef<'_>>
+    where
+        T: Component,
+    {
+        let child = self.world.spawn_one(component).id();
+        self.adopt(child)
+    }
+
+    /// Spawns a child entity with provided bundle of components.
+    ///
+    /// Returns `None` if parent entity was despawned by a hook
+    /// before child is attached to it,
+    /// or if child entity was despawned by a hook
+    /// triggered when relation was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, relation::ChildOf, query::Entities, ExampleComponent};
+    /// let mut world = World::new();
+    /// let parent = world
+    ///     .spawn(())
+    ///     .with_children(|c| {
+    ///         c.spawn((ExampleComponent,))
+    ///             .unwrap()
+    ///             .with_children(|c| {
+    ///                 c.spawn(());
+    ///             });
+    ///     })
+    ///     .unwrap()
+    ///     .id();
+    ///
+    /// assert_eq!(world.view::<Entities>().relates_to::<ChildOf>(parent).iter().count(), 1);
+    /// ```
+    #[inline(always)]
+    pub fn spawn<B>(&mut self, bundle: B) -> Option<EntityRef<'_>>
+    where
+        B: DynamicComponentBundle,
+    {
+        let child = self.world.spawn(bundle).id();
+        self.adopt(child)
+    }
+
+    /// Spawns a child entity with provided bundle of components.
+    ///
+    /// Components must be previously registered.
+    ///
+    /// Returns `None` if parent entity was despawned by a hook
+    /// before child is attached to it,
+    /// or if child entity was despawned by a hook
+    /// triggered when relation was inserted.
+    #[inline(always)]
+    pub fn spawn_external<B>(&mut self, bundle: B) -> Option<EntityRef<'_>>
+    where
+        B: DynamicBundle,
+    {
+        let child = self.world.spawn_external(bundle).id();
+        self.adopt(child)
+    }
+
+    #[inline(always)]
+    fn adopt(&mut self, child: EntityId) -> Option<EntityRef<'_>> {
+        // Fails if parent or child was despawned by a hook.
+        self.world.insert_relation(child, ChildOf, self.parent).ok()?;
+        EntityRef::new(child, self.world).ok()
+    }
+}
@@ -7,7 +7,7 @@ use crate::{action::LocalActionEncoder, entity::EntityId};
 pub use edict_proc::Relation;
 
 pub use self::{
-    child_of::ChildOf,
+    child_of::{ChildOf, ChildSpawner},
     query::{
         FetchFilterRelatedBy, FetchRelatedRead, FetchRelatedWith, FetchRelatedWrite,
         FetchRelatesExclusiveRead, FetchRelatesExclusiveWith, FetchRelatesExclusiveWrite,
@@ -541,4 +541,10 @@ impl Resources {
     pub fn resource_types(&self) -> impl Iterator<Item = TypeId> + '_ {
         self.resources.keys().copied()
     }
+
+    /// Returns type name of the resource.
+    #[inline(always)]
+    pub fn resource_name(&self, ty: TypeId) -> Option<&'static str> {
+        self.resources.get(&ty).map(|r| r.name)
+    }
 }
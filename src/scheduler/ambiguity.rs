@@ -0,0 +1,218 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{any::TypeId, fmt};
+
+use crate::world::World;
+
+use super::{conflicts, DependencyNode, Scheduler};
+
+/// Component accessed by two ambiguously ordered systems.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmbiguousComponent {
+    id: TypeId,
+    name: &'static str,
+    archetypes: Vec<Vec<&'static str>>,
+}
+
+impl AmbiguousComponent {
+    /// Returns type id of the component.
+    #[inline(always)]
+    pub fn id(&self) -> TypeId {
+        self.id
+    }
+
+    /// Returns name of the component.
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns archetypes where access conflicts.
+    /// Each archetype is listed as names of its components.
+    #[inline(always)]
+    pub fn archetypes(&self) -> &[Vec<&'static str>] {
+        &self.archetypes
+    }
+}
+
+/// Pair of systems that have conflicting access
+/// while their relative order is determined only by registration order.
+///
+/// Returned from [`Scheduler::ambiguities`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ambiguity {
+    systems: [DependencyNode; 2],
+    world: bool,
+    resources: Vec<&'static str>,
+    components: Vec<AmbiguousComponent>,
+}
+
+impl Ambiguity {
+    /// Returns the pair of systems, the one registered earlier goes first.
+    #[inline(always)]
+    pub fn systems(&self) -> &[DependencyNode; 2] {
+        &self.systems
+    }
+
+    /// Returns `true` if systems conflict on access to the whole [`World`].
+    #[inline(always)]
+    pub fn world(&self) -> bool {
+        self.world
+    }
+
+    /// Returns names of resources systems conflict on.
+    #[inline(always)]
+    pub fn resources(&self) -> &[&'static str] {
+        &self.resources
+    }
+
+    /// Returns components systems conflict on.
+    #[inline(always)]
+    pub fn components(&self) -> &[AmbiguousComponent] {
+        &self.components
+    }
+}
+
+impl fmt::Display for Ambiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Systems `{}` and `{}` are ordered only by registration order",
+            self.systems[0].name, self.systems[1].name
+        )?;
+        if self.world {
+            f.write_str("\n  conflict on world")?;
+        }
+        for resource in &self.resources {
+            write!(f, "\n  conflict on resource `{resource}`")?;
+        }
+        for component in &self.components {
+            write!(
+                f,
+                "\n  conflict on component `{}` in archetypes",
+                component.name
+            )?;
+            for archetype in &component.archetypes {
+                write!(f, " ({})", join(archetype))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn join(names: &[&str]) -> String {
+    let mut joined = String::new();
+    for (idx, name) in names.iter().enumerate() {
+        if idx > 0 {
+            joined.push_str(", ");
+        }
+        joined.push_str(name);
+    }
+    joined
+}
+
+impl Scheduler {
+    /// Finds pairs of enabled systems that conflict on access
+    /// while their order is not determined by explicit constraints.
+    /// Such systems are executed in order of their registration,
+    /// so reordering `add_system` calls changes the behavior.
+    ///
+    /// Conflicts are computed for the current state of the [`World`].
+    ///
+    /// Barriers added with [`Scheduler::add_apply_actions`] are skipped,
+    /// since they are intended to be ordered by registration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, resources::{Res, ResMut}, scheduler::Scheduler};
+    /// let mut world = World::new();
+    /// world.insert_resource(0u32);
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.add_system(|_: ResMut<u32>| {}).label("writer");
+    /// scheduler.add_system(|_: Res<u32>| {});
+    ///
+    /// let ambiguities = scheduler.ambiguities(&world);
+    /// assert_eq!(ambiguities.len(), 1);
+    /// assert_eq!(ambiguities[0].resources(), ["u32"]);
+    ///
+    /// scheduler.add_system(|_: ResMut<u32>| {}).after("writer");
+    /// assert_eq!(scheduler.ambiguities(&world).len(), 2);
+    /// ```
+    pub fn ambiguities(&mut self, world: &World) -> Vec<Ambiguity> {
+        self.build_or_panic();
+
+        // Transitive closure of explicit constraints.
+        let n = self.systems.len();
+        let mut ordered = vec![vec![false; n]; n];
+        for &i in self.order.iter().rev() {
+            let mut row = vec![false; n];
+            for &j in &self.systems[i].constrained_dependents {
+                row[j] = true;
+                for (r, &o) in row.iter_mut().zip(&ordered[j]) {
+                    *r |= o;
+                }
+            }
+            ordered[i] = row;
+        }
+
+        let mut ambiguities = Vec::new();
+
+        for (i, a) in self.systems.iter().enumerate() {
+            for (j, b) in self.systems.iter().enumerate().skip(i + 1) {
+                if ordered[i][j] || ordered[j][i] {
+                    continue;
+                }
+
+                if !a.enabled || !b.enabled || a.apply_actions || b.apply_actions {
+                    continue;
+                }
+
+                let world_conflict = conflicts(a.world_access(), b.world_access());
+
+                let resources = world
+                    .resource_types()
+                    .filter(|&id| conflicts(a.resource_type_access(id), b.resource_type_access(id)))
+                    .map(|id| world.resource_name(id).unwrap_or("<unknown>"))
+                    .collect::<Vec<_>>();
+
+                let mut components = Vec::<AmbiguousComponent>::new();
+                for archetype in world.archetypes() {
+                    if !a.visit_archetype(archetype) || !b.visit_archetype(archetype) {
+                        continue;
+                    }
+
+                    for info in archetype.infos() {
+                        if !conflicts(
+                            a.component_access(archetype, info),
+                            b.component_access(archetype, info),
+                        ) {
+                            continue;
+                        }
+
+                        let names = archetype.infos().map(|info| info.name()).collect();
+                        match components.iter_mut().find(|c| c.id == info.id()) {
+                            Some(component) => component.archetypes.push(names),
+                            None => components.push(AmbiguousComponent {
+                                id: info.id(),
+                                name: info.name(),
+                                archetypes: vec![names],
+                            }),
+                        }
+                    }
+                }
+
+                if world_conflict || !resources.is_empty() || !components.is_empty() {
+                    ambiguities.push(Ambiguity {
+                        systems: [a.node(), b.node()],
+                        world: world_conflict,
+                        resources,
+                        components,
+                    });
+                }
+            }
+        }
+
+        ambiguities
+    }
+}
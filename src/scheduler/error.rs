@@ -0,0 +1,124 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
+
+use crate::system::SystemError;
+
+use super::SystemId;
+
+/// Error returned from a system run by the [`Scheduler`]
+/// together with the system that returned it.
+///
+/// [`Scheduler`]: super::Scheduler
+#[derive(Debug)]
+pub struct SystemFailure {
+    pub(super) id: SystemId,
+    pub(super) name: &'static str,
+    pub(super) error: SystemError,
+}
+
+impl SystemFailure {
+    /// Returns id of the failed system.
+    #[inline(always)]
+    pub fn id(&self) -> SystemId {
+        self.id
+    }
+
+    /// Returns name of the failed system.
+    /// This is the first label of the system if it has any,
+    /// otherwise type name of the system.
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns error returned from the system.
+    #[inline(always)]
+    pub fn error(&self) -> &SystemError {
+        &self.error
+    }
+
+    /// Returns error returned from the system.
+    #[inline(always)]
+    pub fn into_error(self) -> SystemError {
+        self.error
+    }
+}
+
+impl fmt::Display for SystemFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "System `{}` failed: {}", self.name, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SystemFailure {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Determines what [`Scheduler`] does with errors returned from fallible systems.
+///
+/// [`Scheduler`]: super::Scheduler
+#[derive(Clone, Default)]
+pub enum ErrorHandler {
+    /// Panics with the error message.
+    #[default]
+    Panic,
+
+    /// Prints the error message to standard error.
+    #[cfg(feature = "std")]
+    Log,
+
+    /// Collects errors into [`SystemErrors`] resource.
+    /// Errors are added when actions encoded by systems are executed.
+    Collect,
+
+    /// Calls provided function with the error.
+    /// Function is called from the thread that ran the system.
+    Custom(Arc<dyn Fn(SystemFailure) + Send + Sync>),
+}
+
+impl fmt::Debug for ErrorHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorHandler::Panic => f.write_str("Panic"),
+            #[cfg(feature = "std")]
+            ErrorHandler::Log => f.write_str("Log"),
+            ErrorHandler::Collect => f.write_str("Collect"),
+            ErrorHandler::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Resource with errors collected by [`ErrorHandler::Collect`].
+#[derive(Debug, Default)]
+pub struct SystemErrors {
+    pub(super) failures: Vec<SystemFailure>,
+}
+
+impl SystemErrors {
+    /// Returns `true` if no errors were collected.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns number of collected errors.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns iterator over collected errors.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'_, SystemFailure> {
+        self.failures.iter()
+    }
+
+    /// Removes and returns all collected errors.
+    #[inline(always)]
+    pub fn drain(&mut self) -> alloc::vec::Drain<'_, SystemFailure> {
+        self.failures.drain(..)
+    }
+}
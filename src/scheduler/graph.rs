@@ -0,0 +1,159 @@
+use alloc::{string::String, vec::Vec};
+use core::{any::TypeId, fmt::Write};
+
+use super::SystemId;
+
+/// Reason why one system waits on another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DependencyReason {
+    /// Explicit ordering constraint.
+    Constraint,
+
+    /// Conflicting access to the whole [`World`].
+    ///
+    /// [`World`]: crate::world::World
+    World,
+
+    /// Conflicting access to a resource.
+    Resource {
+        /// Type id of the resource.
+        id: TypeId,
+
+        /// Type name of the resource.
+        name: &'static str,
+    },
+
+    /// Conflicting access to a component.
+    Component {
+        /// Type id of the component.
+        id: TypeId,
+
+        /// Name of the component.
+        name: &'static str,
+    },
+}
+
+/// System in the [`DependencyGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DependencyNode {
+    pub(super) id: SystemId,
+    pub(super) name: &'static str,
+    pub(super) enabled: bool,
+}
+
+impl DependencyNode {
+    /// Returns id of the system.
+    #[inline(always)]
+    pub fn id(&self) -> SystemId {
+        self.id
+    }
+
+    /// Returns name of the system.
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns `true` if system is enabled.
+    #[inline(always)]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Edge of the [`DependencyGraph`].
+/// System [`DependencyEdge::to`] waits for system [`DependencyEdge::from`] to finish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DependencyEdge {
+    pub(super) from: SystemId,
+    pub(super) to: SystemId,
+    pub(super) reason: DependencyReason,
+}
+
+impl DependencyEdge {
+    /// Returns id of the system that runs first.
+    #[inline(always)]
+    pub fn from(&self) -> SystemId {
+        self.from
+    }
+
+    /// Returns id of the system that waits.
+    #[inline(always)]
+    pub fn to(&self) -> SystemId {
+        self.to
+    }
+
+    /// Returns reason of the dependency.
+    #[inline(always)]
+    pub fn reason(&self) -> DependencyReason {
+        self.reason
+    }
+}
+
+/// Dependency graph of the systems in the [`Scheduler`].
+///
+/// Returned from [`Scheduler::dependency_graph`].
+///
+/// [`Scheduler`]: super::Scheduler
+/// [`Scheduler::dependency_graph`]: super::Scheduler::dependency_graph
+#[derive(Clone, Debug)]
+pub struct DependencyGraph {
+    pub(super) nodes: Vec<DependencyNode>,
+    pub(super) edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Returns systems in execution order of sequential run.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[DependencyNode] {
+        &self.nodes
+    }
+
+    /// Returns dependency edges.
+    #[inline(always)]
+    pub fn edges(&self) -> &[DependencyEdge] {
+        &self.edges
+    }
+
+    /// Returns graph in Graphviz DOT format.
+    /// Edges are labeled with their reasons.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph schedule {\n");
+
+        for node in &self.nodes {
+            let style = if node.enabled { "" } else { ", style=dashed" };
+            let _ = writeln!(
+                dot,
+                "    s{} [label=\"{}\"{}];",
+                node.id.0,
+                escape(node.name),
+                style
+            );
+        }
+
+        for edge in &self.edges {
+            let label = match edge.reason {
+                DependencyReason::Constraint => String::from("order"),
+                DependencyReason::World => String::from("world"),
+                DependencyReason::Resource { name, .. } => alloc::format!("resource {name}"),
+                DependencyReason::Component { name, .. } => alloc::format!("component {name}"),
+            };
+            let _ = writeln!(
+                dot,
+                "    s{} -> s{} [label=\"{}\"];",
+                edge.from.0,
+                edge.to.0,
+                escape(&label)
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
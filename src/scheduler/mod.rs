@@ -0,0 +1,2148 @@
+//! Built-in scheduling for systems.
+//!
+//! [`Scheduler`] has following properties:
+//! * Separates execution of conflicting systems temporally.
+//! * Executes non-conflicting systems in parallel on available worker threads.
+//! * Conflicting systems are executed in order of their registration.
+//!   That means that system defines implicit dependency on all systems with with there's conflict.
+//!   * In case of write-to-read conflict, reading system that is added later is guaranteed
+//!     to observe modifications made by writing system that was added before.
+//!   * In case of read-to-write conflict, reading system that is added before is guaranteed
+//!     to NOT observe modifications made by writing system that was added later.
+//!   * In case of write-to-write conflict, writing system that is added before is guaranteed
+//!     to NOT observe modifications made by writing system that was added later.
+//!     And writing system that is added later is guaranteed
+//!     to observe modifications made by writing system that was added before.
+//! * Systems can be labeled and ordered explicitly with [`SystemConfig::before`]
+//!   and [`SystemConfig::after`] constraints.
+//!   Explicit constraints are honored even between systems that do not conflict,
+//!   and override registration order for conflicting systems.
+//!
+
+use alloc::{
+    boxed::Box,
+    collections::{BinaryHeap, VecDeque},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    any::{type_name, TypeId},
+    cell::UnsafeCell,
+    cmp::Reverse,
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use hashbrown::HashSet;
+
+#[derive(Clone)]
+#[repr(transparent)]
+struct Thread {
+    #[cfg(feature = "std")]
+    thread: std::thread::Thread,
+
+    #[cfg(not(feature = "std"))]
+    thread: *mut u8,
+}
+
+unsafe impl Send for Thread {}
+unsafe impl Sync for Thread {}
+
+impl Thread {
+    pub fn unpark(&self) {
+        #[cfg(feature = "std")]
+        self.thread.unpark();
+
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            edict_unpark_thread(self.thread);
+        }
+    }
+
+    pub fn current() -> Self {
+        #[cfg(feature = "std")]
+        let thread = std::thread::current();
+
+        #[cfg(not(feature = "std"))]
+        let thread = unsafe { edict_current_thread() };
+
+        Thread { thread }
+    }
+
+    pub fn park() {
+        #[cfg(feature = "std")]
+        std::thread::park();
+
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            edict_park_thread();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+use parking_lot::Mutex;
+
+/// Current thread parker for no-std environment.
+#[cfg(not(feature = "std"))]
+pub struct CurrentThread;
+
+#[cfg(not(feature = "std"))]
+impl amity::park::DefaultPark for Thread {
+    type Park = CurrentThread;
+    fn default_park() -> CurrentThread {
+        CurrentThread
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl amity::park::Park<Thread> for CurrentThread {
+    fn unpark_token(&self) -> Thread {
+        Thread::current()
+    }
+
+    fn park(&self) {
+        Thread::park();
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl amity::park::Unpark for Thread {
+    fn unpark(&self) {
+        self.unpark();
+    }
+}
+
+#[cfg(not(feature = "std"))]
+type Mutex<T> = lock_api::Mutex<amity::mutex::RawMutex<Thread>, T>;
+
+use crate::{
+    action::ActionBuffer,
+    archetype::Archetype,
+    component::ComponentInfo,
+    executor::ScopedExecutor,
+    system::ActionBufferQueue,
+    system::{union, Condition, IntoCondition, IntoSystem, System, SystemError},
+    world::World,
+    Access,
+};
+
+pub use self::{
+    ambiguity::{Ambiguity, AmbiguousComponent},
+    error::{ErrorHandler, SystemErrors, SystemFailure},
+    graph::{DependencyEdge, DependencyGraph, DependencyNode, DependencyReason},
+    profiler::{Profiler, SystemRun},
+    schedule::{Schedule, Time},
+};
+
+mod ambiguity;
+mod error;
+mod graph;
+mod profiler;
+mod schedule;
+
+#[cfg(feature = "std")]
+mod pool;
+
+#[cfg(feature = "std")]
+pub use self::pool::{PoolScope, ThreadPool, ThreadPoolBuilder};
+
+#[cfg(not(feature = "std"))]
+use crate::nostd::scheduler::{edict_current_thread, edict_park_thread, edict_unpark_thread};
+
+/// Scheduler that starts systems in order of their registration.
+/// And executes as many non-conflicting systems in parallel as possible.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, resources::Res, scheduler::Scheduler, system::IntoSystem};
+///
+/// let mut world = World::new();
+/// let mut scheduler = Scheduler::new();
+///
+/// scheduler.add_system(|| {});
+/// scheduler.add_system(|world: &mut World| {
+///   println!("{}", world.with_resource::<i32>(|| 0));
+/// });
+/// scheduler.add_system(|world: &World| {
+///   assert_eq!(0, *world.expect_resource::<i32>());
+/// });
+/// scheduler.add_system(|res: Res<i32>| {
+///   assert_eq!(0, *res);
+/// });
+///
+/// scheduler.run_threaded(&mut world);
+/// ```
+pub struct Scheduler {
+    systems: Vec<ScheduledSystem>,
+    next_id: u64,
+    order: Vec<usize>,
+    order_valid: bool,
+    schedule_cache_id: Option<u64>,
+
+    /// Archetype set id for which run conditions were checked to be read-only.
+    conditions_cache_id: Option<u64>,
+    action_buffers: Vec<ActionBuffer>,
+    deterministic: bool,
+    profiler: Option<Arc<dyn Profiler>>,
+    error_handler: ErrorHandler,
+
+    /// Dependency edges with reasons, computed by `reschedule`.
+    edges: Vec<(usize, usize, DependencyReason)>,
+
+    #[cfg(feature = "std")]
+    thread_pool: Option<Arc<ThreadPool>>,
+}
+
+/// Configures system added to the [`Scheduler`].
+///
+/// Returned from [`Scheduler::add_system`] and [`Scheduler::add_boxed_system`].
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, scheduler::Scheduler};
+/// let mut world = World::new();
+/// let mut scheduler = Scheduler::new();
+///
+/// scheduler.add_system(|| println!("physics")).label("physics").after("input");
+/// scheduler.add_system(|| println!("input")).label("input");
+///
+/// scheduler.run_sequential(&mut world);
+/// ```
+pub struct SystemConfig<'a> {
+    scheduler: &'a mut Scheduler,
+    idx: usize,
+}
+
+impl SystemConfig<'_> {
+    /// Returns id of the system.
+    /// It can be used to remove, replace, enable or disable the system later.
+    #[must_use]
+    pub fn id(&self) -> SystemId {
+        self.scheduler.systems[self.idx].id
+    }
+
+    /// Adds label to the system.
+    /// System may have any number of labels
+    /// and any number of systems may share the same label.
+    pub fn label(self, label: &'static str) -> Self {
+        self.with(|system| system.labels.push(label))
+    }
+
+    /// Requires this system to run before all systems with the specified label.
+    pub fn before(self, label: &'static str) -> Self {
+        self.with(|system| system.before.push(label))
+    }
+
+    /// Requires this system to run after all systems with the specified label.
+    pub fn after(self, label: &'static str) -> Self {
+        self.with(|system| system.after.push(label))
+    }
+
+    /// Adds run condition to the system.
+    /// System runs only if all its conditions return `true`.
+    ///
+    /// Condition must be a read-only system that returns `bool`,
+    /// e.g. a function with [`Res`] arguments.
+    /// Conditions are checked right before the system runs
+    /// and their access is taken into account when systems are scheduled.
+    /// Scheduler panics on run if condition has write access.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, resources::Res, scheduler::Scheduler};
+    /// let mut world = World::new();
+    /// world.insert_resource(true);
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .add_system(|| -> () { unreachable!() })
+    ///     .run_if(|paused: Res<bool>| !*paused);
+    ///
+    /// scheduler.run_sequential(&mut world);
+    /// ```
+    ///
+    /// [`Res`]: crate::resources::Res
+    pub fn run_if<M>(self, condition: impl IntoCondition<M>) -> Self {
+        let condition = condition.into_condition();
+        self.with(|system| {
+            system.conditions.get_mut().push(Box::new(condition));
+            system.initialized = false;
+            system.update_is_local();
+        })
+    }
+
+    fn with(self, f: impl FnOnce(&mut ScheduledSystem)) -> Self {
+        f(&mut self.scheduler.systems[self.idx]);
+        self.scheduler.order_valid = false;
+        self.scheduler.schedule_cache_id = None;
+        self.scheduler.conditions_cache_id = None;
+        self
+    }
+}
+
+/// Identifier of a system in the [`Scheduler`].
+///
+/// Returned from [`SystemConfig::id`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemId(u64);
+
+/// Error returned from [`Scheduler::build`]
+/// when explicit ordering constraints form a cycle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError {
+    systems: Vec<&'static str>,
+}
+
+impl CycleError {
+    /// Returns names of the systems that form the cycle, in execution order
+    /// required by the constraints.
+    /// Labeled systems are named by their first label.
+    pub fn systems(&self) -> &[&'static str] {
+        &self.systems
+    }
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cycle in system ordering constraints: ")?;
+        for name in &self.systems {
+            write!(f, "{name} -> ")?;
+        }
+        match self.systems.first() {
+            Some(name) => f.write_str(name),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CycleError {}
+
+struct SyncUnsafeCell<T: ?Sized> {
+    inner: UnsafeCell<T>,
+}
+
+impl<T> SyncUnsafeCell<T> {
+    pub fn new(value: T) -> Self {
+        SyncUnsafeCell {
+            inner: UnsafeCell::new(value),
+        }
+    }
+}
+
+unsafe impl<T: ?Sized> Sync for SyncUnsafeCell<T> {}
+
+impl<T: ?Sized> Deref for SyncUnsafeCell<T> {
+    type Target = UnsafeCell<T>;
+
+    fn deref(&self) -> &UnsafeCell<T> {
+        &self.inner
+    }
+}
+
+impl<T: ?Sized> DerefMut for SyncUnsafeCell<T> {
+    fn deref_mut(&mut self) -> &mut UnsafeCell<T> {
+        &mut self.inner
+    }
+}
+
+struct ScheduledSystem {
+    id: SystemId,
+    enabled: bool,
+
+    /// Barrier that executes actions recorded by preceding systems.
+    apply_actions: bool,
+
+    /// Whether the system and its conditions were initialized.
+    initialized: bool,
+    system: SyncUnsafeCell<Box<dyn System + Send>>,
+    conditions: SyncUnsafeCell<Vec<Box<dyn Condition + Send>>>,
+
+    /// Buffers recorded by the system in deterministic mode.
+    actions: SyncUnsafeCell<Vec<ActionBuffer>>,
+    wait: AtomicUsize,
+    dependents: Vec<usize>,
+    dependencies: usize,
+    is_local: bool,
+    name: &'static str,
+    labels: Vec<&'static str>,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+
+    /// Systems that must run after this one due to explicit constraints.
+    constrained_dependents: Vec<usize>,
+}
+
+impl ScheduledSystem {
+    /// Runs the system if all its conditions are met.
+    ///
+    /// # Safety
+    ///
+    /// Caller must ensure that system is not running concurrently
+    /// with itself or conflicting systems.
+    /// Local system must be run on the thread where [`World`] lives.
+    unsafe fn run_unchecked(
+        &self,
+        world: NonNull<World>,
+        queue: &mut impl ExecuteActions,
+        profiler: Option<&dyn Profiler>,
+        error_handler: &ErrorHandler,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.apply_actions {
+            if unsafe { self.check_conditions(world, queue) } {
+                // Safety: Barrier is local and all other systems either finished
+                // or wait for it.
+                let world = unsafe { &mut *world.as_ptr() };
+                if let Some(profiler) = profiler {
+                    profiler.begin_flush();
+                    queue.execute_all(world);
+                    profiler.end_flush();
+                } else {
+                    queue.execute_all(world);
+                }
+            }
+            return;
+        }
+
+        let result = if let Some(profiler) = profiler {
+            let run = SystemRun {
+                id: self.id,
+                name: self.name(),
+            };
+            profiler.begin_system(&run);
+            let result = unsafe { self.run_conditional(world, queue) };
+            profiler.end_system(&run);
+            result
+        } else {
+            unsafe { self.run_conditional(world, queue) }
+        };
+
+        if let Err(error) = result {
+            let failure = SystemFailure {
+                id: self.id,
+                name: self.name(),
+                error,
+            };
+            unsafe { handle_error(error_handler, failure, world, queue) };
+        }
+    }
+
+    /// Runs the system if all its conditions are met.
+    unsafe fn run_conditional(
+        &self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> Result<(), SystemError> {
+        if !unsafe { self.check_conditions(world, queue) } {
+            return Ok(());
+        }
+
+        let system = unsafe { &mut *self.system.get() };
+        unsafe { system.try_run_unchecked(world, queue) }
+    }
+
+    /// Initializes the system and its conditions if not yet initialized.
+    fn init(&mut self, world: &mut World) {
+        if !self.initialized {
+            self.system.get_mut().init(world);
+            for condition in self.conditions.get_mut() {
+                condition.init(world);
+            }
+            self.initialized = true;
+        }
+    }
+
+    /// Checks if all conditions of the system are met.
+    unsafe fn check_conditions(
+        &self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> bool {
+        let conditions = unsafe { &mut *self.conditions.get() };
+        conditions
+            .iter_mut()
+            .all(|condition| unsafe { condition.check_unchecked(world, queue) })
+    }
+
+    // Access methods below are used only when scheduler is borrowed mutably,
+    // so the system and its conditions are not running.
+
+    fn world_access(&self) -> Option<Access> {
+        let system = unsafe { &*self.system.get() };
+        let conditions = unsafe { &*self.conditions.get() };
+        conditions
+            .iter()
+            .fold(system.world_access(), |access, condition| {
+                union(access, condition.world_access())
+            })
+    }
+
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        let system = unsafe { &*self.system.get() };
+        let conditions = unsafe { &*self.conditions.get() };
+        conditions
+            .iter()
+            .fold(system.resource_type_access(ty), |access, condition| {
+                union(access, condition.resource_type_access(ty))
+            })
+    }
+
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        let system = unsafe { &*self.system.get() };
+        let conditions = unsafe { &*self.conditions.get() };
+        system.visit_archetype(archetype)
+            || conditions
+                .iter()
+                .any(|condition| condition.visit_archetype(archetype))
+    }
+
+    fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+        let system = unsafe { &*self.system.get() };
+        let conditions = unsafe { &*self.conditions.get() };
+
+        let mut access = None;
+        if system.visit_archetype(archetype) {
+            access = system.component_access(archetype, comp);
+        }
+        for condition in conditions.iter() {
+            if condition.visit_archetype(archetype) {
+                access = union(access, condition.component_access(archetype, comp));
+            }
+        }
+        access
+    }
+
+    /// Checks that conditions do not write anything.
+    fn check_conditions_read_only(&self, world: &World) {
+        let conditions = unsafe { &*self.conditions.get() };
+
+        for condition in conditions.iter() {
+            let mut read_only = condition.world_access() != Some(Access::Write);
+
+            read_only &= world
+                .resource_types()
+                .all(|ty| condition.resource_type_access(ty) != Some(Access::Write));
+
+            read_only &= world.archetypes().iter().all(|archetype| {
+                !condition.visit_archetype(archetype)
+                    || archetype.infos().all(|info| {
+                        condition.component_access(archetype, info) != Some(Access::Write)
+                    })
+            });
+
+            if !read_only {
+                panic!(
+                    "Run condition of system `{}` must be read-only",
+                    self.name()
+                );
+            }
+        }
+    }
+
+    fn update_is_local(&mut self) {
+        let system = self.system.get_mut();
+        let conditions = self.conditions.get_mut();
+        self.is_local = system.is_local() || conditions.iter().any(|c| c.is_local());
+    }
+
+    fn node(&self) -> DependencyNode {
+        DependencyNode {
+            id: self.id,
+            name: self.name(),
+            enabled: self.enabled,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.labels.first() {
+            Some(label) => label,
+            None => self.name,
+        }
+    }
+
+    /// Checks if explicit constraints require this system to run before `other`.
+    fn runs_before(&self, other: &ScheduledSystem) -> bool {
+        self.before.iter().any(|label| other.labels.contains(label))
+            || other.after.iter().any(|label| self.labels.contains(label))
+    }
+}
+
+struct QueueInner<T> {
+    items: Mutex<VecDeque<T>>,
+    thread: Thread,
+}
+
+struct Queue<T> {
+    inner: Arc<QueueInner<T>>,
+}
+
+impl<T> Clone for Queue<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Queue {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if Arc::strong_count(&self.inner) == 2 {
+            self.inner.thread.unpark();
+        }
+    }
+}
+
+impl<T> Queue<T> {
+    #[inline(always)]
+    fn new() -> Self {
+        Queue {
+            inner: Arc::new(QueueInner {
+                items: Mutex::new(VecDeque::new()),
+                thread: Thread::current(),
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn enqueue(&self, item: T) {
+        self.inner.items.lock().push_back(item);
+        self.inner.thread.unpark();
+    }
+
+    #[inline(always)]
+    fn try_deque(&self) -> Option<T> {
+        self.inner.items.lock().pop_front()
+    }
+
+    #[inline(always)]
+    fn deque(&self) -> Result<T, ()> {
+        loop {
+            if let Some(item) = self.try_deque() {
+                return Ok(item);
+            }
+            if Arc::strong_count(&self.inner) == 1 {
+                return Err(());
+            }
+            Thread::park();
+        }
+    }
+}
+
+/// Action buffer queue that can execute buffers flushed so far.
+trait ExecuteActions: ActionBufferQueue {
+    fn execute_all(&mut self, world: &mut World);
+}
+
+impl ExecuteActions for Vec<ActionBuffer> {
+    #[inline(always)]
+    fn execute_all(&mut self, world: &mut World) {
+        use crate::action::ActionBufferSliceExt;
+        self.as_mut_slice().execute_all(world);
+    }
+}
+
+impl ExecuteActions for Queue<ActionBuffer> {
+    #[inline(always)]
+    fn execute_all(&mut self, world: &mut World) {
+        use crate::action::ActionBufferSliceExt;
+
+        let mut buffers = core::mem::take(&mut *self.inner.items.lock());
+        buffers.make_contiguous().execute_all(world);
+
+        // Return empty buffers for reuse.
+        self.inner.items.lock().extend(buffers);
+    }
+}
+
+impl ActionBufferQueue for Queue<ActionBuffer> {
+    #[inline(always)]
+    fn get<'a>(&self) -> ActionBuffer {
+        // Taking last ensures that commands from system won't be executed before commands
+        // of its transient dependencies.
+        if let Some(last) = self.inner.items.lock().pop_back() {
+            last
+        } else {
+            ActionBuffer::new()
+        }
+    }
+
+    #[inline(always)]
+    fn flush(&mut self, buffer: ActionBuffer) {
+        self.enqueue(buffer);
+    }
+}
+
+/// Queue that keeps buffers flushed by a system in its own slot,
+/// so they can be executed in registration order of systems.
+struct OrderedQueue<'a> {
+    idx: usize,
+    systems: &'a [ScheduledSystem],
+    pool: &'a Queue<ActionBuffer>,
+}
+
+impl ActionBufferQueue for OrderedQueue<'_> {
+    #[inline(always)]
+    fn get(&self) -> ActionBuffer {
+        self.pool.get()
+    }
+
+    #[inline(always)]
+    fn flush(&mut self, buffer: ActionBuffer) {
+        // Safety: Only the task that runs this system accesses its slot.
+        let actions = unsafe { &mut *self.systems[self.idx].actions.get() };
+        actions.push(buffer);
+    }
+}
+
+impl ExecuteActions for OrderedQueue<'_> {
+    fn execute_all(&mut self, world: &mut World) {
+        for system in self.systems {
+            // Safety: Barrier runs when no other system is running.
+            let actions = unsafe { &mut *system.actions.get() };
+            for mut buffer in actions.drain(..) {
+                buffer.execute(world);
+                self.pool.enqueue(buffer);
+            }
+        }
+    }
+}
+
+/// Moves buffers recorded by systems in deterministic mode to `buffers`
+/// in registration order of systems.
+///
+/// # Safety
+///
+/// Systems must not be running.
+unsafe fn collect_ordered(systems: &[ScheduledSystem], buffers: &mut Vec<ActionBuffer>) {
+    for system in systems {
+        buffers.append(unsafe { &mut *system.actions.get() });
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NonNullWorld {
+    ptr: NonNull<World>,
+}
+
+unsafe impl Send for NonNullWorld {}
+
+struct Task<'scope> {
+    system_idx: usize,
+    systems: &'scope [ScheduledSystem],
+    profiler: Option<&'scope dyn Profiler>,
+    error_handler: &'scope ErrorHandler,
+    deterministic: bool,
+    world: NonNullWorld,
+    task_queue: Queue<Task<'scope>>,
+    action_queue: Queue<ActionBuffer>,
+}
+
+impl<'scope> Task<'scope> {
+    fn run(self, executor: &impl ScopedExecutor<'scope>) {
+        let Task {
+            system_idx,
+            systems,
+            profiler,
+            error_handler,
+            deterministic,
+            world,
+            task_queue,
+            mut action_queue,
+        } = self;
+
+        let mut unroll = Some(system_idx);
+
+        while let Some(idx) = unroll.take() {
+            unsafe {
+                // # Safety
+                //
+                // Only spawned task or task that decrements zeroed wait counter
+                // gets to run this system.
+                if deterministic {
+                    let mut queue = OrderedQueue {
+                        idx,
+                        systems,
+                        pool: &action_queue,
+                    };
+                    systems[idx].run_unchecked(world.ptr, &mut queue, profiler, error_handler);
+                } else {
+                    systems[idx].run_unchecked(
+                        world.ptr,
+                        &mut action_queue,
+                        profiler,
+                        error_handler,
+                    );
+                }
+            }
+
+            for &dependent_idx in &systems[idx].dependents {
+                let old = systems[dependent_idx].wait.fetch_sub(1, Ordering::AcqRel);
+                if old == 0 {
+                    let is_local = systems[dependent_idx].is_local;
+
+                    if !is_local && unroll.is_none() {
+                        unroll = Some(dependent_idx);
+                    } else {
+                        let task = Task {
+                            system_idx: dependent_idx,
+                            systems: systems,
+                            profiler,
+                            error_handler,
+                            deterministic,
+                            world: world,
+                            task_queue: task_queue.clone(),
+                            action_queue: action_queue.clone(),
+                        };
+                        if is_local {
+                            task_queue.enqueue(task);
+                        } else {
+                            executor.spawn(move |executor| task.run(executor));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Scheduler {
+    /// Creates new empty scheduler.
+    pub fn new() -> Self {
+        Scheduler {
+            systems: Vec::new(),
+            next_id: 0,
+            order: Vec::new(),
+            order_valid: true,
+            schedule_cache_id: None,
+            conditions_cache_id: None,
+            action_buffers: Vec::new(),
+            deterministic: false,
+            profiler: None,
+            error_handler: ErrorHandler::Panic,
+            edges: Vec::new(),
+
+            #[cfg(feature = "std")]
+            thread_pool: None,
+        }
+    }
+
+    /// Adds system to the scheduler.
+    ///
+    /// Returned [`SystemConfig`] can be used to label the system,
+    /// order it relative to other systems and get its [`SystemId`].
+    pub fn add_system<M, S>(&mut self, system: S) -> SystemConfig<'_>
+    where
+        S: IntoSystem<M>,
+    {
+        self.push_system(Box::new(system.into_system()), type_name::<S>())
+    }
+
+    /// Adds system to the scheduler.
+    ///
+    /// Returned [`SystemConfig`] can be used to label the system,
+    /// order it relative to other systems and get its [`SystemId`].
+    pub fn add_boxed_system(&mut self, system: Box<dyn System + Send>) -> SystemConfig<'_> {
+        self.push_system(system, "<boxed system>")
+    }
+
+    fn push_system(
+        &mut self,
+        system: Box<dyn System + Send>,
+        name: &'static str,
+    ) -> SystemConfig<'_> {
+        let idx = self.systems.len();
+        let id = SystemId(self.next_id);
+        self.next_id += 1;
+
+        self.systems.push(ScheduledSystem {
+            id,
+            enabled: true,
+            apply_actions: false,
+            initialized: false,
+            is_local: system.is_local(),
+            system: SyncUnsafeCell::new(system),
+            conditions: SyncUnsafeCell::new(Vec::new()),
+            actions: SyncUnsafeCell::new(Vec::new()),
+            wait: AtomicUsize::new(0),
+            dependents: Vec::new(),
+            dependencies: 0,
+            name,
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+            constrained_dependents: Vec::new(),
+        });
+        self.order_valid = false;
+        self.schedule_cache_id = None;
+        SystemConfig {
+            scheduler: self,
+            idx,
+        }
+    }
+
+    /// Adds barrier that executes actions recorded by all systems that run before it.
+    /// Systems that run after the barrier observe effects of those actions.
+    ///
+    /// Barrier runs on the thread where [`World`] lives,
+    /// after all systems that precede it in the schedule and before all systems that follow.
+    /// It can be labeled and ordered like any other system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, action::ActionEncoder, scheduler::Scheduler, view::View, ExampleComponent};
+    /// let mut world = World::new();
+    /// let mut scheduler = Scheduler::new();
+    ///
+    /// scheduler.add_system(|mut encoder: ActionEncoder| {
+    ///     encoder.spawn_one(ExampleComponent);
+    /// });
+    /// scheduler.add_apply_actions();
+    /// scheduler.add_system(|view: View<&ExampleComponent>| {
+    ///     assert_eq!(view.iter().count(), 1);
+    /// });
+    ///
+    /// scheduler.run_sequential(&mut world);
+    /// ```
+    pub fn add_apply_actions(&mut self) -> SystemConfig<'_> {
+        let config = self.push_system(Box::new(ApplyActions), "apply_actions");
+        config.scheduler.systems[config.idx].apply_actions = true;
+        config
+    }
+
+    /// Removes system from the scheduler.
+    /// Returns removed system or `None` if there is no such system.
+    ///
+    /// Labels of removed system no longer participate in ordering constraints.
+    pub fn remove_system(&mut self, id: SystemId) -> Option<Box<dyn System + Send>> {
+        let idx = self.system_idx(id)?;
+        let system = self.systems.remove(idx);
+        self.order_valid = false;
+        self.schedule_cache_id = None;
+        Some(system.system.inner.into_inner())
+    }
+
+    /// Enables or disables system.
+    /// Returns `false` if there is no such system.
+    ///
+    /// Disabled system does not run and does not conflict with other systems.
+    /// Its ordering constraints are still honored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, scheduler::Scheduler};
+    /// let mut world = World::new();
+    /// let mut scheduler = Scheduler::new();
+    ///
+    /// let id = scheduler.add_system(|| -> () { unreachable!() }).id();
+    /// scheduler.set_enabled(id, false);
+    ///
+    /// scheduler.run_sequential(&mut world);
+    /// ```
+    pub fn set_enabled(&mut self, id: SystemId, enabled: bool) -> bool {
+        let Some(idx) = self.system_idx(id) else {
+            return false;
+        };
+        let system = &mut self.systems[idx];
+        if system.enabled != enabled {
+            system.enabled = enabled;
+            self.schedule_cache_id = None;
+        }
+        true
+    }
+
+    /// Returns `true` if system is enabled.
+    /// Returns `false` if system is disabled or there is no such system.
+    #[must_use]
+    pub fn is_enabled(&self, id: SystemId) -> bool {
+        match self.system_idx(id) {
+            Some(idx) => self.systems[idx].enabled,
+            None => false,
+        }
+    }
+
+    /// Replaces system keeping its id, labels, ordering constraints and run conditions.
+    /// Returns replaced system or `None` if there is no such system.
+    pub fn replace_system<M, S>(
+        &mut self,
+        id: SystemId,
+        system: S,
+    ) -> Option<Box<dyn System + Send>>
+    where
+        S: IntoSystem<M>,
+    {
+        let idx = self.system_idx(id)?;
+        let scheduled = &mut self.systems[idx];
+        let old = core::mem::replace(scheduled.system.get_mut(), Box::new(system.into_system()));
+        scheduled.name = type_name::<S>();
+        scheduled.initialized = false;
+        scheduled.update_is_local();
+        self.schedule_cache_id = None;
+        Some(old)
+    }
+
+    /// Returns dependency graph of the systems computed for the current state of the [`World`].
+    ///
+    /// Each edge tells which system waits on which and why.
+    /// Edges implied by other edges transitively are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, resources::{Res, ResMut}, scheduler::{DependencyReason, Scheduler}};
+    /// let mut world = World::new();
+    /// world.insert_resource(0u32);
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.add_system(|_: ResMut<u32>| {}).label("writer");
+    /// scheduler.add_system(|_: Res<u32>| {}).label("reader");
+    ///
+    /// let graph = scheduler.dependency_graph(&world);
+    /// assert_eq!(graph.edges().len(), 1);
+    /// assert!(matches!(graph.edges()[0].reason(), DependencyReason::Resource { .. }));
+    ///
+    /// println!("{}", graph.to_dot());
+    /// ```
+    pub fn dependency_graph(&mut self, world: &World) -> DependencyGraph {
+        self.reschedule(world);
+
+        let nodes = self
+            .order
+            .iter()
+            .map(|&idx| self.systems[idx].node())
+            .collect();
+
+        let edges = self
+            .edges
+            .iter()
+            .map(|&(from, to, reason)| DependencyEdge {
+                from: self.systems[from].id,
+                to: self.systems[to].id,
+                reason,
+            })
+            .collect();
+
+        DependencyGraph { nodes, edges }
+    }
+
+    /// Sets profiler that receives events from all `run_*` methods.
+    /// Replaces previously set profiler.
+    pub fn set_profiler(&mut self, profiler: Arc<dyn Profiler>) {
+        self.profiler = Some(profiler);
+    }
+
+    /// Sets handler for errors returned from fallible systems.
+    ///
+    /// By default scheduler panics on system error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, scheduler::{ErrorHandler, Scheduler, SystemErrors}};
+    /// let mut world = World::new();
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.set_error_handler(ErrorHandler::Collect);
+    ///
+    /// scheduler
+    ///     .add_system(|| -> Result<(), &'static str> { Err("oops") })
+    ///     .label("failing");
+    ///
+    /// scheduler.run_sequential(&mut world);
+    ///
+    /// let errors = world.expect_resource::<SystemErrors>();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors.iter().next().unwrap().to_string(), "System `failing` failed: oops");
+    /// ```
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) {
+        self.error_handler = handler;
+    }
+
+    /// Removes profiler.
+    pub fn remove_profiler(&mut self) -> Option<Arc<dyn Profiler>> {
+        self.profiler.take()
+    }
+
+    fn system_idx(&self, id: SystemId) -> Option<usize> {
+        self.systems.iter().position(|system| system.id == id)
+    }
+
+    /// Builds execution order of the systems from explicit ordering constraints.
+    ///
+    /// Systems are ordered by registration unless constraints require otherwise.
+    /// Returns error if constraints form a cycle.
+    ///
+    /// This method is called implicitly by `run_*` methods,
+    /// which panic if constraints form a cycle.
+    pub fn build(&mut self) -> Result<(), CycleError> {
+        if self.order_valid {
+            return Ok(());
+        }
+
+        let mut wait = Vec::with_capacity(self.systems.len());
+        for i in 0..self.systems.len() {
+            let mut constrained_dependents = Vec::new();
+            for j in 0..self.systems.len() {
+                if i != j && self.systems[i].runs_before(&self.systems[j]) {
+                    constrained_dependents.push(j);
+                }
+            }
+            self.systems[i].constrained_dependents = constrained_dependents;
+            wait.push(0);
+        }
+
+        for system in &self.systems {
+            for &j in &system.constrained_dependents {
+                wait[j] += 1;
+            }
+        }
+
+        // Kahn's algorithm that prefers earlier registered systems.
+        let mut ready = BinaryHeap::new();
+        for (idx, &wait) in wait.iter().enumerate() {
+            if wait == 0 {
+                ready.push(Reverse(idx));
+            }
+        }
+
+        self.order.clear();
+        while let Some(Reverse(idx)) = ready.pop() {
+            self.order.push(idx);
+            for &j in &self.systems[idx].constrained_dependents {
+                wait[j] -= 1;
+                if wait[j] == 0 {
+                    ready.push(Reverse(j));
+                }
+            }
+        }
+
+        if self.order.len() < self.systems.len() {
+            return Err(self.find_cycle(&wait));
+        }
+
+        self.order_valid = true;
+        Ok(())
+    }
+
+    /// Finds a cycle among systems that were not ordered.
+    /// Each of them has at least one unordered predecessor.
+    fn find_cycle(&self, wait: &[usize]) -> CycleError {
+        let mut path = Vec::new();
+        let mut cur = wait.iter().position(|&wait| wait > 0).unwrap();
+
+        let start = loop {
+            if let Some(pos) = path.iter().position(|&idx| idx == cur) {
+                break pos;
+            }
+            path.push(cur);
+            cur = (0..self.systems.len())
+                .find(|&j| wait[j] > 0 && self.systems[j].constrained_dependents.contains(&cur))
+                .unwrap();
+        };
+
+        let mut cycle = path.split_off(start);
+        cycle.reverse();
+
+        // Start from the earliest registered system.
+        let first = (0..cycle.len()).min_by_key(|&k| cycle[k]).unwrap();
+        cycle.rotate_left(first);
+
+        let systems = cycle.iter().map(|&idx| self.systems[idx].name()).collect();
+
+        CycleError { systems }
+    }
+
+    fn build_or_panic(&mut self) {
+        if let Err(err) = self.build() {
+            panic!("{err}");
+        }
+    }
+
+    /// Runs all systems in the scheduler using worker threads of the [`ThreadPool`].
+    ///
+    /// If thread pool is not set with [`Scheduler::set_thread_pool`],
+    /// default one is created on first call and reused afterwards.
+    #[cfg(feature = "std")]
+    pub fn run_threaded(&mut self, world: &mut World) {
+        let profiler = self.profiler.clone();
+        let pool = self.thread_pool().clone();
+        let buffers = pool.scope(|scope| self.run_with(world, &scope));
+        flush(buffers, world, profiler.as_deref());
+    }
+
+    /// Enables or disables deterministic application of actions.
+    ///
+    /// By default action buffers are reused across systems
+    /// and executed in order of completion of systems that recorded them,
+    /// which may vary between runs of multithreaded scheduler.
+    ///
+    /// In deterministic mode actions recorded by each system
+    /// are executed in registration order of systems,
+    /// regardless of execution timing.
+    /// This applies to [`Scheduler::add_apply_actions`] barriers as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, action::ActionEncoder, scheduler::Scheduler};
+    /// let mut world = World::new();
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.set_deterministic(true);
+    ///
+    /// scheduler.add_system(|mut encoder: ActionEncoder| {
+    ///     encoder.insert_resource(1u32);
+    /// });
+    /// scheduler.add_system(|mut encoder: ActionEncoder| {
+    ///     encoder.insert_resource(2u32);
+    /// });
+    ///
+    /// scheduler.run_threaded(&mut world);
+    /// assert_eq!(*world.expect_resource::<u32>(), 2);
+    /// ```
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Sets thread pool used by [`Scheduler::run_threaded`].
+    /// Same pool may be shared by multiple schedulers.
+    #[cfg(feature = "std")]
+    pub fn set_thread_pool(&mut self, pool: Arc<ThreadPool>) {
+        self.thread_pool = Some(pool);
+    }
+
+    /// Returns thread pool used by [`Scheduler::run_threaded`].
+    /// Creates default one if not set.
+    #[cfg(feature = "std")]
+    pub fn thread_pool(&mut self) -> &Arc<ThreadPool> {
+        self.thread_pool
+            .get_or_insert_with(|| Arc::new(ThreadPool::default()))
+    }
+
+    /// Runs all systems in the scheduler using rayon.
+    #[cfg(feature = "rayon")]
+    pub fn run_rayon(&mut self, world: &mut World) {
+        let profiler = self.profiler.clone();
+        let buffers = rayon::in_place_scope(|scope| self.run_with(world, scope));
+        flush(buffers, world, profiler.as_deref());
+    }
+
+    /// Runs all systems in the scheduler sequentially.
+    pub fn run_sequential(&mut self, world: &mut World) {
+        // let buffers = self.run_with(world, &mut MockExecutor);
+        // buffers.execute_all(world);
+
+        self.build_or_panic();
+
+        for system in &mut self.systems {
+            system.init(world);
+        }
+
+        self.check_conditions(world);
+
+        let mut buffers = Vec::new();
+        if self.deterministic {
+            let pool = Queue::new();
+            for &idx in &self.order {
+                let mut queue = OrderedQueue {
+                    idx,
+                    systems: &self.systems,
+                    pool: &pool,
+                };
+                unsafe {
+                    // # Safety
+                    //
+                    // Systems run one by one on the current thread.
+                    self.systems[idx].run_unchecked(
+                        NonNull::from(&mut *world),
+                        &mut queue,
+                        self.profiler.as_deref(),
+                        &self.error_handler,
+                    );
+                }
+            }
+            // Safety: All systems finished running.
+            unsafe { collect_ordered(&self.systems, &mut buffers) };
+        } else {
+            for &idx in &self.order {
+                unsafe {
+                    // # Safety
+                    //
+                    // Systems run one by one on the current thread.
+                    self.systems[idx].run_unchecked(
+                        NonNull::from(&mut *world),
+                        &mut buffers,
+                        self.profiler.as_deref(),
+                        &self.error_handler,
+                    );
+                }
+            }
+        }
+        flush(&mut buffers, world, self.profiler.as_deref());
+    }
+
+    /// Runs all systems in the scheduler.
+    /// Provided closure should spawn system execution task.
+    ///
+    /// Running systems on the current thread instead can be viable for debugging purposes.
+    ///
+    /// Returns action buffers that caller should execute.
+    /// Profiler receives events of system runs but not of buffer execution.
+    #[must_use]
+    pub fn run_with<'scope, 'later: 'scope>(
+        &'later mut self,
+        world: &'scope mut World,
+        executor: &impl ScopedExecutor<'scope>,
+    ) -> &'later mut [ActionBuffer] {
+        for system in &mut self.systems {
+            system.init(world);
+        }
+
+        self.reschedule(world);
+
+        for system in &mut self.systems {
+            *system.wait.get_mut() = system.dependencies;
+        }
+
+        let task_queue = Queue::new();
+        let action_queue = Queue::new();
+
+        for buffer in self.action_buffers.drain(..) {
+            action_queue.enqueue(buffer);
+        }
+
+        let mut unroll = None;
+
+        let world_ptr = NonNull::from(world);
+
+        for (idx, system) in self.systems.iter().enumerate() {
+            let old = system.wait.fetch_sub(1, Ordering::Acquire);
+            if old == 0 {
+                let is_local = system.is_local;
+                let task = Task {
+                    system_idx: idx,
+                    world: NonNullWorld { ptr: world_ptr },
+                    systems: &self.systems,
+                    profiler: self.profiler.as_deref(),
+                    error_handler: &self.error_handler,
+                    deterministic: self.deterministic,
+                    task_queue: task_queue.clone(),
+                    action_queue: action_queue.clone(),
+                };
+                if is_local {
+                    if unroll.is_none() {
+                        unroll = Some(task);
+                    } else {
+                        task_queue.enqueue(task);
+                    }
+                } else {
+                    executor.spawn(move |executor| task.run(executor));
+                }
+            }
+        }
+
+        if let Some(task) = unroll {
+            task.run(executor);
+        }
+
+        while let Ok(task) = task_queue.deque() {
+            task.run(executor);
+        }
+
+        while let Ok(buffer) = action_queue.deque() {
+            self.action_buffers.push(buffer);
+        }
+
+        if self.deterministic {
+            // Safety: All tasks are complete once action queue is drained.
+            unsafe { collect_ordered(&self.systems, &mut self.action_buffers) };
+        }
+
+        &mut self.action_buffers[..]
+    }
+
+    /// Checks that run conditions of all systems are read-only.
+    fn check_conditions(&mut self, world: &World) {
+        if self.conditions_cache_id == Some(world.archetype_set_id()) {
+            return;
+        }
+
+        for system in &self.systems {
+            system.check_conditions_read_only(world);
+        }
+
+        self.conditions_cache_id = Some(world.archetype_set_id());
+    }
+
+    fn reschedule(&mut self, world: &World) {
+        if self.schedule_cache_id == Some(world.archetype_set_id()) {
+            return;
+        }
+
+        self.build_or_panic();
+
+        self.check_conditions(world);
+
+        self.edges.clear();
+
+        // Systems are analyzed in execution order.
+        // Each system may depend only on systems that precede it.
+        for p in 0..self.order.len() {
+            let i = self.order[p];
+
+            // Reset dependencies.
+            let a = &mut self.systems[i];
+            a.dependents.clear();
+            a.dependencies = 0;
+
+            let mut deps = HashSet::new();
+
+            'j: for q in (0..p).rev() {
+                let j = self.order[q];
+                let a = &self.systems[i];
+                let b = &self.systems[j];
+
+                for &d in &b.dependents {
+                    if deps.contains(&d) {
+                        // A transitive dependency.
+                        deps.insert(j);
+                        continue 'j;
+                    }
+                }
+
+                if b.constrained_dependents.contains(&i) {
+                    // Explicit constraint.
+                    // Add a dependency.
+                    self.edges.push((j, i, DependencyReason::Constraint));
+                    self.systems[j].dependents.push(i);
+                    self.systems[i].dependencies += 1;
+                    deps.insert(j);
+                    continue 'j;
+                }
+
+                if !a.enabled || !b.enabled {
+                    // Disabled systems do not conflict.
+                    continue 'j;
+                }
+
+                if a.apply_actions
+                    || b.apply_actions
+                    || conflicts(a.world_access(), b.world_access())
+                {
+                    // Conflicts on world access.
+                    // Barrier conflicts with all systems.
+                    // Add a dependency.
+                    self.edges.push((j, i, DependencyReason::World));
+                    self.systems[j].dependents.push(i);
+                    self.systems[i].dependencies += 1;
+                    deps.insert(j);
+                    continue 'j;
+                }
+
+                for id in world.resource_types() {
+                    if conflicts(a.resource_type_access(id), b.resource_type_access(id)) {
+                        // Conflicts on this resource.
+                        // Add a dependency.
+                        let name = world.resource_name(id).unwrap_or("<unknown>");
+                        self.edges
+                            .push((j, i, DependencyReason::Resource { id, name }));
+                        self.systems[j].dependents.push(i);
+                        self.systems[i].dependencies += 1;
+                        deps.insert(j);
+                        continue 'j;
+                    }
+                }
+
+                for archetype in world.archetypes() {
+                    if !a.visit_archetype(archetype) || !b.visit_archetype(archetype) {
+                        // Ignore skipped archetypes.
+                        continue;
+                    }
+
+                    for info in archetype.infos() {
+                        if conflicts(
+                            a.component_access(archetype, info),
+                            b.component_access(archetype, info),
+                        ) {
+                            // Conflicts on this archetype.
+                            // Add a dependency.
+                            self.edges.push((
+                                j,
+                                i,
+                                DependencyReason::Component {
+                                    id: info.id(),
+                                    name: info.name(),
+                                },
+                            ));
+                            self.systems[j].dependents.push(i);
+                            self.systems[i].dependencies += 1;
+                            deps.insert(j);
+                            continue 'j;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Placeholder system for actions barrier.
+/// Scheduler executes action buffers in its place.
+struct ApplyActions;
+
+unsafe impl System for ApplyActions {
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Write)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn component_access(&self, _archetype: &Archetype, _comp: &ComponentInfo) -> Option<Access> {
+        Some(Access::Write)
+    }
+
+    #[inline(always)]
+    fn resource_type_access(&self, _ty: TypeId) -> Option<Access> {
+        Some(Access::Write)
+    }
+
+    #[inline(always)]
+    unsafe fn run_unchecked(&mut self, _world: NonNull<World>, _queue: &mut dyn ActionBufferQueue) {
+    }
+}
+
+/// Passes system failure to the handler.
+///
+/// # Safety
+///
+/// Must be called right after the failed system ran,
+/// with the same `world` and `queue`.
+unsafe fn handle_error(
+    handler: &ErrorHandler,
+    failure: SystemFailure,
+    world: NonNull<World>,
+    queue: &mut dyn ActionBufferQueue,
+) {
+    match handler {
+        ErrorHandler::Panic => panic!("{failure}"),
+        #[cfg(feature = "std")]
+        ErrorHandler::Log => std::eprintln!("{failure}"),
+        ErrorHandler::Collect => {
+            let mut buffer = queue.get();
+            buffer
+                .encoder(unsafe { world.as_ref() })
+                .closure(move |world| {
+                    world
+                        .with_default_resource::<SystemErrors>()
+                        .failures
+                        .push(failure);
+                });
+            queue.flush(buffer);
+        }
+        ErrorHandler::Custom(f) => f(failure),
+    }
+}
+
+/// Executes actions from the buffers reporting to the profiler.
+fn flush(buffers: &mut [ActionBuffer], world: &mut World, profiler: Option<&dyn Profiler>) {
+    use crate::action::ActionBufferSliceExt;
+
+    if let Some(profiler) = profiler {
+        profiler.begin_flush();
+        buffers.execute_all(world);
+        profiler.end_flush();
+    } else {
+        buffers.execute_all(world);
+    }
+}
+
+fn conflicts(lhs: Option<Access>, rhs: Option<Access>) -> bool {
+    matches!(
+        (lhs, rhs),
+        (Some(Access::Write), Some(_)) | (Some(_), Some(Access::Write))
+    )
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use crate::system::State;
+
+    #[test]
+    fn test() {
+        let mut world = World::new();
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|mut q: State<i32>| {
+            *q = 11;
+        });
+
+        scheduler.run_sequential(&mut world);
+    }
+
+    #[test]
+    fn explicit_order() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut world = World::new();
+        let mut scheduler = Scheduler::new();
+
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let c = counter.clone();
+        scheduler
+            .add_system(move || {
+                assert_eq!(c.fetch_add(1, Ordering::SeqCst) % 3, 2);
+            })
+            .label("physics")
+            .after("input");
+
+        let c = counter.clone();
+        scheduler
+            .add_system(move || {
+                assert_eq!(c.fetch_add(1, Ordering::SeqCst) % 3, 0);
+            })
+            .before("physics")
+            .before("input");
+
+        let c = counter.clone();
+        scheduler
+            .add_system(move || {
+                assert_eq!(c.fetch_add(1, Ordering::SeqCst) % 3, 1);
+            })
+            .label("input");
+
+        scheduler.run_sequential(&mut world);
+
+        #[cfg(feature = "std")]
+        scheduler.run_threaded(&mut world);
+
+        assert_eq!(scheduler.build(), Ok(()));
+    }
+
+    #[test]
+    fn run_condition() {
+        use crate::resources::{Res, ResMut};
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(false);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|mut flag: ResMut<bool>| *flag = !*flag);
+        scheduler
+            .add_system(|mut count: ResMut<u32>| *count += 1)
+            .run_if(|flag: Res<bool>| *flag);
+
+        for _ in 0..4 {
+            scheduler.run_sequential(&mut world);
+        }
+        assert_eq!(*world.expect_resource::<u32>(), 2);
+
+        #[cfg(feature = "std")]
+        for _ in 0..4 {
+            scheduler.run_threaded(&mut world);
+        }
+        #[cfg(feature = "std")]
+        assert_eq!(*world.expect_resource::<u32>(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_condition_read_only() {
+        use crate::resources::ResMut;
+
+        let mut world = World::new();
+        world.insert_resource(false);
+
+        let mut scheduler = Scheduler::new();
+        scheduler
+            .add_system(|| {})
+            .run_if(|flag: ResMut<bool>| *flag);
+
+        #[cfg(feature = "std")]
+        scheduler.run_threaded(&mut world);
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = scheduler.run_with(&mut world, &crate::executor::MockExecutor);
+        }
+    }
+
+    #[test]
+    #[should_panic = "must be read-only"]
+    fn run_condition_read_only_sequential() {
+        use crate::resources::ResMut;
+
+        let mut world = World::new();
+        world.insert_resource(false);
+
+        let mut scheduler = Scheduler::new();
+        scheduler
+            .add_system(|| {})
+            .run_if(|flag: ResMut<bool>| *flag);
+
+        scheduler.run_sequential(&mut world);
+    }
+
+    #[test]
+    fn system_management() {
+        use crate::resources::ResMut;
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut scheduler = Scheduler::new();
+        let a = scheduler
+            .add_system(|mut count: ResMut<u32>| *count += 1)
+            .label("a")
+            .id();
+        let b = scheduler
+            .add_system(|mut count: ResMut<u32>| *count *= 10)
+            .after("a")
+            .id();
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u32>(), 10);
+
+        assert!(scheduler.set_enabled(b, false));
+        assert!(!scheduler.is_enabled(b));
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u32>(), 11);
+
+        assert!(scheduler.set_enabled(b, true));
+        assert!(scheduler
+            .replace_system(a, |mut count: ResMut<u32>| *count += 2)
+            .is_some());
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u32>(), 130);
+
+        assert!(scheduler.remove_system(a).is_some());
+        assert!(scheduler.remove_system(a).is_none());
+        assert!(!scheduler.set_enabled(a, true));
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u32>(), 1300);
+    }
+
+    #[test]
+    fn component_ambiguity() {
+        use crate::{component::Component, view::View};
+
+        struct Foo;
+        impl Component for Foo {}
+        struct Bar;
+        impl Component for Bar {}
+
+        let mut world = World::new();
+        world.spawn((Foo, Bar));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: View<&mut Foo>| {}).label("write");
+        scheduler
+            .add_system(|_: View<(&Foo, &Bar)>| {})
+            .label("read");
+
+        let ambiguities = scheduler.ambiguities(&world);
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].systems()[0].name(), "write");
+        assert_eq!(ambiguities[0].components().len(), 1);
+        assert_eq!(ambiguities[0].components()[0].archetypes().len(), 1);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: View<&mut Foo>| {}).label("write");
+        scheduler
+            .add_system(|_: View<(&Foo, &Bar)>| {})
+            .after("write");
+        assert!(scheduler.ambiguities(&world).is_empty());
+
+        // Barriers are ordered by registration.
+        scheduler.add_apply_actions();
+        scheduler.add_system(|_: View<&Bar>| {}).after("write");
+        assert!(scheduler.ambiguities(&world).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn thread_pool_reuse() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut world = World::new();
+        let mut scheduler = Scheduler::new();
+        scheduler.set_thread_pool(Arc::new(ThreadPool::new(3)));
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        for _ in 0..8 {
+            let counter = counter.clone();
+            scheduler.add_system(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        for _ in 0..100 {
+            scheduler.run_threaded(&mut world);
+        }
+
+        assert_eq!(counter.load(Ordering::Relaxed), 800);
+        assert_eq!(scheduler.thread_pool().threads(), 3);
+    }
+
+    #[test]
+    fn explicit_order_cycle() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.add_system(|| {}).label("a").after("c");
+        scheduler.add_system(|| {}).label("b").after("a");
+        scheduler.add_system(|| {}).label("c").after("b");
+        scheduler.add_system(|| {}).label("d").after("c");
+
+        let err = scheduler.build().unwrap_err();
+        assert_eq!(err.systems(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn apply_actions_barrier() {
+        use crate::{action::ActionEncoder, component::Component, resources::ResMut, view::View};
+
+        #[derive(Component)]
+        struct Spawned;
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|mut encoder: ActionEncoder| {
+            encoder.spawn_one(Spawned);
+        });
+        scheduler.add_apply_actions();
+        scheduler.add_system(|view: View<&Spawned>, mut seen: ResMut<usize>| {
+            *seen = view.iter().count();
+        });
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<usize>(), 1);
+
+        #[cfg(feature = "std")]
+        {
+            scheduler.run_threaded(&mut world);
+            assert_eq!(*world.expect_resource::<usize>(), 2);
+        }
+
+        let graph = scheduler.dependency_graph(&world);
+        assert_eq!(graph.edges().len(), 2);
+        assert!(graph
+            .edges()
+            .iter()
+            .all(|edge| edge.reason() == DependencyReason::World));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn deterministic_actions() {
+        use crate::{action::ActionEncoder, component::Component};
+
+        #[derive(Component)]
+        struct Idx(usize);
+
+        let mut world = World::new();
+        let mut scheduler = Scheduler::new();
+        scheduler.set_deterministic(true);
+
+        for i in 0..8 {
+            scheduler.add_system(move |mut encoder: ActionEncoder| {
+                encoder.spawn_one(Idx(i));
+            });
+        }
+        scheduler.add_apply_actions();
+        for i in 8..16 {
+            scheduler.add_system(move |mut encoder: ActionEncoder| {
+                encoder.spawn_one(Idx(i));
+            });
+        }
+
+        for run in 1..=10 {
+            scheduler.run_threaded(&mut world);
+
+            let order = world
+                .view::<&Idx>()
+                .iter()
+                .map(|idx| idx.0)
+                .collect::<Vec<_>>();
+            let expected = (0..run).flat_map(|_| 0..16).collect::<Vec<_>>();
+            assert_eq!(order, expected);
+        }
+    }
+
+    #[test]
+    fn pipe_access() {
+        use crate::{
+            resources::{Res, ResMut},
+            system::{In, IntoPipeSystem},
+        };
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(0u64);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: ResMut<u64>| {});
+        scheduler.add_system((|value: Res<u32>| *value + 1).pipe(
+            |In(value): In<u32>, mut out: ResMut<u64>| {
+                *out = value.into();
+            },
+        ));
+
+        let graph = scheduler.dependency_graph(&world);
+        assert_eq!(graph.edges().len(), 1);
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u64>(), 1);
+    }
+
+    #[test]
+    fn fallible_pipe() {
+        use crate::system::{In, IntoPipeSystem};
+
+        let mut world = World::new();
+
+        let mut scheduler = Scheduler::new();
+        scheduler.set_error_handler(ErrorHandler::Collect);
+        scheduler
+            .add_system((|| 1u32).pipe(|In(value): In<u32>| -> Result<(), u32> { Err(value) }));
+        scheduler.add_system(
+            (|| 2u32)
+                .pipe(|In(value): In<u32>| value + 1)
+                .pipe(|In(value): In<u32>| -> Result<(), u32> { Err(value) }),
+        );
+        scheduler.add_system((|| 3u32).pipe(|In(_): In<u32>| -> Result<(), u32> { Ok(()) }));
+
+        scheduler.run_sequential(&mut world);
+
+        let mut errors = world.expect_resource_mut::<SystemErrors>();
+        let mut failures = errors
+            .drain()
+            .map(|failure| *failure.error().downcast_ref::<u32>().unwrap())
+            .collect::<Vec<_>>();
+        failures.sort();
+        assert_eq!(failures, [1, 3]);
+    }
+
+    #[test]
+    fn fallible_systems() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        use crate::resources::ResMut;
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.set_error_handler(ErrorHandler::Collect);
+
+        scheduler
+            .add_system((
+                |mut res: ResMut<u32>| -> Result<(), &'static str> {
+                    *res += 1;
+                    Err("first")
+                },
+                |mut res: ResMut<u32>| -> Result<(), &'static str> {
+                    *res += 10;
+                    Ok(())
+                },
+            ))
+            .label("sequence");
+        scheduler.add_system(|| -> Result<(), &'static str> { Ok(()) });
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u32>(), 1);
+
+        let mut errors = world.expect_resource_mut::<SystemErrors>();
+        let failures = errors.drain().collect::<Vec<_>>();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name(), "sequence");
+        assert_eq!(failures[0].error().downcast_ref::<&str>(), Some(&"first"));
+        drop(errors);
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        scheduler.set_error_handler(ErrorHandler::Custom(Arc::new(move |failure| {
+            assert_eq!(failure.name(), "sequence");
+            counter.fetch_add(1, Ordering::Relaxed);
+        })));
+
+        #[cfg(feature = "std")]
+        scheduler.run_threaded(&mut world);
+        #[cfg(not(feature = "std"))]
+        scheduler.run_sequential(&mut world);
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(world.expect_resource::<SystemErrors>().is_empty());
+    }
+
+    #[test]
+    fn optional_resources() {
+        use crate::{
+            resources::{Res, ResMut},
+            system::{ResInit, ResMutInit},
+        };
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|a: Option<Res<u32>>, b: Option<ResMut<u64>>| {
+            assert!(a.is_some());
+            assert!(b.is_none());
+        });
+        scheduler.add_system(|mut a: ResMutInit<u8>, b: ResInit<u16>| {
+            *a += 1;
+            assert_eq!(*b, 0);
+        });
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<u8>(), 1);
+
+        world.insert_resource(5u8);
+
+        #[cfg(feature = "std")]
+        scheduler.run_threaded(&mut world);
+        #[cfg(not(feature = "std"))]
+        scheduler.run_sequential(&mut world);
+
+        assert_eq!(*world.expect_resource::<u8>(), 6);
+    }
+
+    #[test]
+    fn modified_since_last_run() {
+        use crate::{
+            component::Component,
+            epoch::EpochId,
+            query::{EpochOf, Modified},
+            resources::ResMut,
+            view::View,
+        };
+
+        #[derive(Component)]
+        struct Value(u32);
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<EpochId>::new());
+        world.insert_resource(0usize);
+        let e = world.spawn((Value(0),)).id();
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(
+            |view: View<(Modified<&Value>, EpochOf<Value>)>, mut seen: ResMut<Vec<EpochId>>| {
+                seen.clear();
+                seen.extend(view.iter().map(|(_, epoch)| epoch));
+            },
+        );
+        scheduler.add_system(
+            |mut view: View<Modified<Option<&mut Value>>>, mut count: ResMut<usize>| {
+                *count = view.iter_mut().flatten().count();
+            },
+        );
+
+        scheduler.run_sequential(&mut world);
+        assert_eq!(world.expect_resource::<Vec<EpochId>>().len(), 1);
+        assert_eq!(*world.expect_resource::<usize>(), 1);
+
+        scheduler.run_sequential(&mut world);
+        assert!(world.expect_resource::<Vec<EpochId>>().is_empty());
+        assert_eq!(*world.expect_resource::<usize>(), 0);
+
+        world.get::<&mut Value>(e).unwrap().0 = 1;
+        let epoch = world.epoch();
+        scheduler.run_sequential(&mut world);
+        assert_eq!(*world.expect_resource::<Vec<EpochId>>(), [epoch]);
+        assert_eq!(*world.expect_resource::<usize>(), 1);
+    }
+
+    #[test]
+    fn any_of_access() {
+        use crate::{component::Component, query::AnyOf, view::View};
+
+        #[derive(Component)]
+        struct A;
+
+        #[derive(Component)]
+        struct B;
+
+        #[derive(Component)]
+        struct C;
+
+        let mut world = World::new();
+        world.spawn((A, B));
+        world.spawn((C,));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: View<AnyOf<(&A, &mut B)>>| {});
+        scheduler.add_system(|_: View<&A>| {});
+        assert!(scheduler.dependency_graph(&world).edges().is_empty());
+
+        scheduler.add_system(|_: View<&B>| {});
+        assert_eq!(scheduler.dependency_graph(&world).edges().len(), 1);
+
+        scheduler.add_system(|_: View<&mut C>| {});
+        assert_eq!(scheduler.dependency_graph(&world).edges().len(), 1);
+    }
+
+    #[test]
+    fn dynamic_view_access() {
+        use crate::{
+            component::Component,
+            query::{DynamicQuery, DynamicQuerySource, DynamicTerm, DynamicView},
+            view::View,
+        };
+
+        #[derive(Component)]
+        struct A;
+
+        #[derive(Component)]
+        struct B;
+
+        struct WriteA;
+
+        impl DynamicQuerySource for WriteA {
+            fn query() -> DynamicQuery {
+                DynamicQuery::new([DynamicTerm::write::<A>()])
+            }
+        }
+
+        let mut world = World::new();
+        world.spawn((A, B));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: DynamicView<WriteA>| {});
+        scheduler.add_system(|_: View<&B>| {});
+        assert!(scheduler.dependency_graph(&world).edges().is_empty());
+
+        scheduler.add_system(|_: View<&A>| {});
+        assert_eq!(scheduler.dependency_graph(&world).edges().len(), 1);
+    }
+}
@@ -0,0 +1,325 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use core::{
+    any::Any,
+    cell::Cell,
+    marker::PhantomData,
+    panic::AssertUnwindSafe,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use std::thread::{self, JoinHandle, Thread};
+
+use parking_lot::{Condvar, Mutex};
+
+use crate::executor::ScopedExecutor;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+std::thread_local! {
+    /// Pool and index of the worker running on this thread.
+    static WORKER: Cell<(*const Shared, usize)> = const { Cell::new((ptr::null(), 0)) };
+}
+
+struct Shared {
+    injector: Mutex<VecDeque<Job>>,
+    locals: Vec<Mutex<VecDeque<Job>>>,
+    queued: AtomicUsize,
+    sleep: Mutex<()>,
+    wake: Condvar,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn push(&self, job: Job) {
+        let (pool, idx) = WORKER.with(Cell::get);
+        if ptr::eq(pool, self) {
+            self.locals[idx].lock().push_back(job);
+        } else {
+            self.injector.lock().push_back(job);
+        }
+
+        self.queued.fetch_add(1, Ordering::SeqCst);
+
+        // Taking the lock ensures that worker
+        // that found no jobs is already waiting.
+        let _guard = self.sleep.lock();
+        self.wake.notify_one();
+    }
+
+    /// Pops job from worker's own queue, then from injector,
+    /// and finally steals from other workers.
+    fn find_job(&self, worker: Option<usize>) -> Option<Job> {
+        let job = worker
+            .and_then(|idx| self.locals[idx].lock().pop_back())
+            .or_else(|| self.injector.lock().pop_front())
+            .or_else(|| {
+                let start = worker.map_or(0, |idx| idx + 1);
+                (0..self.locals.len())
+                    .map(|offset| (start + offset) % self.locals.len())
+                    .filter(|&idx| Some(idx) != worker)
+                    .find_map(|idx| self.locals[idx].lock().pop_front())
+            });
+
+        if job.is_some() {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+        }
+        job
+    }
+
+    fn run_worker(self: Arc<Self>, idx: usize) {
+        WORKER.with(|worker| worker.set((Arc::as_ptr(&self), idx)));
+
+        loop {
+            if let Some(job) = self.find_job(Some(idx)) {
+                job();
+                continue;
+            }
+
+            let mut guard = self.sleep.lock();
+            if self.shutdown.load(Ordering::SeqCst) {
+                return;
+            }
+            if self.queued.load(Ordering::SeqCst) == 0 {
+                self.wake.wait(&mut guard);
+            }
+        }
+    }
+}
+
+/// Builder for [`ThreadPool`].
+#[derive(Clone, Debug, Default)]
+pub struct ThreadPoolBuilder {
+    threads: Option<usize>,
+
+    #[cfg(feature = "core_affinity")]
+    pin_to_cores: bool,
+}
+
+impl ThreadPoolBuilder {
+    /// Returns new builder with default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets number of worker threads.
+    /// By default it is one less than available parallelism,
+    /// as the thread that runs the scheduler executes systems too.
+    ///
+    /// Pool always has at least one worker thread.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Pins each worker thread to a separate CPU core when possible.
+    #[cfg(feature = "core_affinity")]
+    pub fn pin_to_cores(mut self, pin: bool) -> Self {
+        self.pin_to_cores = pin;
+        self
+    }
+
+    /// Spawns worker threads and returns the pool.
+    #[must_use]
+    pub fn build(self) -> ThreadPool {
+        let threads = self.threads.unwrap_or_else(|| {
+            thread::available_parallelism().map_or(1, |n| n.get().saturating_sub(1).max(1))
+        });
+
+        let shared = Arc::new(Shared {
+            injector: Mutex::new(VecDeque::new()),
+            locals: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+            queued: AtomicUsize::new(0),
+            sleep: Mutex::new(()),
+            wake: Condvar::new(),
+            shutdown: AtomicBool::new(false),
+        });
+
+        #[cfg(feature = "core_affinity")]
+        let core_ids = match self.pin_to_cores {
+            true => core_affinity::get_core_ids().unwrap_or_default(),
+            false => Vec::new(),
+        };
+
+        let workers = (0..threads)
+            .map(|idx| {
+                let shared = shared.clone();
+
+                #[cfg(feature = "core_affinity")]
+                let core_id = match core_ids.is_empty() {
+                    true => None,
+                    false => Some(core_ids[idx % core_ids.len()]),
+                };
+
+                thread::Builder::new()
+                    .name(alloc::format!("edict-worker-{idx}"))
+                    .spawn(move || {
+                        #[cfg(feature = "core_affinity")]
+                        if let Some(core_id) = core_id {
+                            core_affinity::set_for_current(core_id);
+                        }
+
+                        shared.run_worker(idx)
+                    })
+                    .expect("Failed to spawn worker thread")
+            })
+            .collect();
+
+        ThreadPool { shared, workers }
+    }
+}
+
+/// Pool of persistent worker threads that steal work from each other.
+///
+/// [`Scheduler::run_threaded`] uses thread pool owned by the [`Scheduler`],
+/// so threads are spawned once and reused across runs.
+///
+/// [`Scheduler`]: super::Scheduler
+/// [`Scheduler::run_threaded`]: super::Scheduler::run_threaded
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        ThreadPoolBuilder::new().build()
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        {
+            let _guard = self.shared.sleep.lock();
+            self.shared.shutdown.store(true, Ordering::SeqCst);
+            self.shared.wake.notify_all();
+        }
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl ThreadPool {
+    /// Returns new pool with specified number of worker threads.
+    #[must_use]
+    pub fn new(threads: usize) -> Self {
+        ThreadPoolBuilder::new().threads(threads).build()
+    }
+
+    /// Returns builder for [`ThreadPool`].
+    #[must_use]
+    pub fn builder() -> ThreadPoolBuilder {
+        ThreadPoolBuilder::new()
+    }
+
+    /// Returns number of worker threads.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Creates a scope for spawning tasks that may borrow non-`'static` data.
+    /// Returns when all tasks spawned in the scope are complete.
+    /// Calling thread executes pending tasks while waiting.
+    ///
+    /// If any task panics, panic is propagated after all tasks are complete.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// # use edict::{executor::ScopedExecutor, scheduler::ThreadPool};
+    /// let pool = ThreadPool::new(2);
+    /// let counter = AtomicUsize::new(0);
+    ///
+    /// pool.scope(|scope| {
+    ///     for _ in 0..10 {
+    ///         scope.spawn(|_| {
+    ///             counter.fetch_add(1, Ordering::Relaxed);
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(counter.load(Ordering::Relaxed), 10);
+    /// ```
+    pub fn scope<'env, F, R>(&self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope PoolScope<'scope, 'env>) -> R,
+    {
+        let scope = PoolScope {
+            shared: &self.shared,
+            pending: AtomicUsize::new(0),
+            panic: Mutex::new(None),
+            thread: thread::current(),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+        let worker = WORKER.with(Cell::get);
+        let worker = match ptr::eq(worker.0, Arc::as_ptr(&self.shared)) {
+            true => Some(worker.1),
+            false => None,
+        };
+
+        while scope.pending.load(Ordering::Acquire) != 0 {
+            match self.shared.find_job(worker) {
+                Some(job) => job(),
+                None => thread::park(),
+            }
+        }
+
+        if let Some(payload) = scope.panic.lock().take() {
+            std::panic::resume_unwind(payload);
+        }
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+}
+
+/// Scope for spawning tasks on [`ThreadPool`].
+///
+/// Created by [`ThreadPool::scope`].
+pub struct PoolScope<'scope, 'env: 'scope> {
+    shared: &'scope Arc<Shared>,
+    pending: AtomicUsize,
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+    thread: Thread,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope> ScopedExecutor<'scope> for &'scope PoolScope<'scope, '_> {
+    fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce(&Self) + Send + 'scope,
+    {
+        let scope = *self;
+        scope.pending.fetch_add(1, Ordering::Relaxed);
+
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(|| f(&scope))) {
+                scope.panic.lock().get_or_insert(payload);
+            }
+
+            // Scope may be gone right after `pending` reaches zero.
+            let thread = scope.thread.clone();
+            if scope.pending.fetch_sub(1, Ordering::AcqRel) == 1 {
+                thread.unpark();
+            }
+        });
+
+        // Safety: `ThreadPool::scope` does not return
+        // until all jobs spawned in the scope are complete.
+        let job: Job =
+            unsafe { core::mem::transmute::<Box<dyn FnOnce() + Send + 'scope>, Job>(job) };
+
+        scope.shared.push(job);
+    }
+}
@@ -0,0 +1,84 @@
+use super::SystemId;
+
+/// Receives events from the [`Scheduler`] to measure time spent in systems
+/// and in execution of actions they encoded.
+///
+/// Events of a system run are reported from the thread that runs the system.
+/// Time between end of one system and beginning of the next one on the same thread
+/// is spent by the scheduler itself.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use edict::{world::World, scheduler::{Profiler, Scheduler, SystemRun}};
+/// #[derive(Default)]
+/// struct Log(Mutex<Vec<String>>);
+///
+/// impl Profiler for Log {
+///     fn begin_system(&self, run: &SystemRun<'_>) {
+///         self.0.lock().unwrap().push(format!("begin {}", run.name()));
+///     }
+///
+///     fn end_system(&self, run: &SystemRun<'_>) {
+///         self.0.lock().unwrap().push(format!("end {}", run.name()));
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system(|| {}).label("noop");
+///
+/// let log = std::sync::Arc::new(Log::default());
+/// scheduler.set_profiler(log.clone());
+/// scheduler.run_sequential(&mut world);
+///
+/// assert_eq!(*log.0.lock().unwrap(), ["begin noop", "end noop"]);
+/// ```
+///
+/// [`Scheduler`]: super::Scheduler
+pub trait Profiler: Send + Sync {
+    /// Called right before system (including its run conditions) starts running.
+    fn begin_system(&self, run: &SystemRun<'_>);
+
+    /// Called right after system finishes running.
+    fn end_system(&self, run: &SystemRun<'_>);
+
+    /// Called before actions encoded by systems are executed.
+    #[inline(always)]
+    fn begin_flush(&self) {}
+
+    /// Called after actions encoded by systems are executed.
+    #[inline(always)]
+    fn end_flush(&self) {}
+}
+
+/// Information about system run reported to [`Profiler`].
+#[derive(Clone, Copy, Debug)]
+pub struct SystemRun<'a> {
+    pub(super) id: SystemId,
+    pub(super) name: &'a str,
+}
+
+impl<'a> SystemRun<'a> {
+    /// Returns id of the system.
+    #[inline(always)]
+    pub fn id(&self) -> SystemId {
+        self.id
+    }
+
+    /// Returns name of the system.
+    /// This is the first label of the system if it has any,
+    /// otherwise type name of the system.
+    #[inline(always)]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns id of the thread that runs the system.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn thread(&self) -> std::thread::ThreadId {
+        std::thread::current().id()
+    }
+}
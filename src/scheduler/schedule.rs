@@ -0,0 +1,300 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::{system::IntoSystem, world::World};
+
+use super::{Scheduler, SystemConfig};
+
+#[cfg(feature = "std")]
+use {super::ThreadPool, alloc::sync::Arc};
+
+/// Resource that tracks time passed between frames.
+///
+/// Application advances it once per frame with [`Time::advance`]
+/// before running [`Schedule`].
+/// Fixed-timestep stages of the [`Schedule`] accumulate [`Time::delta`]
+/// to decide how many times to run per frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Time {
+    delta: Duration,
+    elapsed: Duration,
+}
+
+impl Time {
+    /// Returns new [`Time`] with no time passed.
+    #[must_use]
+    pub const fn new() -> Self {
+        Time {
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Advances time by the specified duration.
+    #[inline(always)]
+    pub fn advance(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+    }
+
+    /// Returns duration of the last frame.
+    #[inline(always)]
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns total time passed.
+    #[inline(always)]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+struct FixedStep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+struct Stage {
+    name: &'static str,
+    scheduler: Scheduler,
+    fixed: Option<FixedStep>,
+}
+
+/// Sequence of named stages, each with its own [`Scheduler`].
+///
+/// Stages run one after another in order of their addition.
+/// Actions encoded by systems of a stage are executed
+/// before the next stage starts.
+///
+/// Fixed-timestep stages run zero or more times per [`Schedule`] run,
+/// once per each full step accumulated from [`Time::delta`].
+///
+/// # Example
+///
+/// ```
+/// # use core::time::Duration;
+/// # use edict::{world::World, resources::ResMut, scheduler::{Schedule, Time}};
+/// let mut world = World::new();
+/// world.insert_resource(Time::new());
+/// world.insert_resource(0u32);
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_fixed_stage("FixedUpdate", Duration::from_millis(10));
+/// schedule.add_system("FixedUpdate", |mut steps: ResMut<u32>| *steps += 1);
+///
+/// world.expect_resource_mut::<Time>().advance(Duration::from_millis(25));
+/// schedule.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<u32>(), 2);
+///
+/// world.expect_resource_mut::<Time>().advance(Duration::from_millis(5));
+/// schedule.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<u32>(), 3);
+/// ```
+pub struct Schedule {
+    stages: Vec<Stage>,
+    max_fixed_steps: Option<u32>,
+}
+
+impl Default for Schedule {
+    /// Returns schedule with [`Schedule::PRE_UPDATE`], [`Schedule::UPDATE`]
+    /// and [`Schedule::POST_UPDATE`] stages.
+    fn default() -> Self {
+        let mut schedule = Schedule::new();
+        schedule.add_stage(Schedule::PRE_UPDATE);
+        schedule.add_stage(Schedule::UPDATE);
+        schedule.add_stage(Schedule::POST_UPDATE);
+        schedule
+    }
+}
+
+impl Schedule {
+    /// Name of the stage that runs before [`Schedule::UPDATE`] in default schedule.
+    pub const PRE_UPDATE: &'static str = "PreUpdate";
+
+    /// Name of the main stage in default schedule.
+    pub const UPDATE: &'static str = "Update";
+
+    /// Name of the stage that runs after [`Schedule::UPDATE`] in default schedule.
+    pub const POST_UPDATE: &'static str = "PostUpdate";
+
+    /// Returns new schedule without stages.
+    #[must_use]
+    pub fn new() -> Self {
+        Schedule {
+            stages: Vec::new(),
+            max_fixed_steps: None,
+        }
+    }
+
+    /// Adds stage that runs once per schedule run.
+    /// Stage is added after all existing stages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if stage with the same name already exists.
+    pub fn add_stage(&mut self, name: &'static str) -> &mut Scheduler {
+        self.push_stage(name, None)
+    }
+
+    /// Adds stage that runs once per each `step` of time accumulated from [`Time::delta`].
+    /// Stage is added after all existing stages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if stage with the same name already exists.
+    /// Panics if `step` is zero.
+    ///
+    /// Running the schedule with fixed stages panics
+    /// if [`Time`] resource is not present in the [`World`].
+    pub fn add_fixed_stage(&mut self, name: &'static str, step: Duration) -> &mut Scheduler {
+        assert!(
+            !step.is_zero(),
+            "Fixed stage `{name}` must have non-zero step"
+        );
+        self.push_stage(
+            name,
+            Some(FixedStep {
+                step,
+                accumulator: Duration::ZERO,
+            }),
+        )
+    }
+
+    /// Limits number of times each fixed-timestep stage may run per schedule run.
+    /// Whole steps that exceed the limit are discarded,
+    /// remainder of the step is kept.
+    ///
+    /// This prevents schedule from falling behind
+    /// when fixed stages take longer to run than their step.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use edict::{world::World, resources::ResMut, scheduler::{Schedule, Time}};
+    /// let mut world = World::new();
+    /// world.insert_resource(Time::new());
+    /// world.insert_resource(0u32);
+    ///
+    /// let mut schedule = Schedule::new();
+    /// schedule.add_fixed_stage("Physics", Duration::from_millis(10));
+    /// schedule.add_system("Physics", |mut steps: ResMut<u32>| *steps += 1);
+    /// schedule.set_max_fixed_steps(1);
+    ///
+    /// world.expect_resource_mut::<Time>().advance(Duration::from_millis(25));
+    /// schedule.run_sequential(&mut world);
+    /// assert_eq!(*world.expect_resource::<u32>(), 1);
+    ///
+    /// world.expect_resource_mut::<Time>().advance(Duration::from_millis(5));
+    /// schedule.run_sequential(&mut world);
+    /// assert_eq!(*world.expect_resource::<u32>(), 2);
+    /// ```
+    pub fn set_max_fixed_steps(&mut self, max_steps: u32) {
+        self.max_fixed_steps = Some(max_steps);
+    }
+
+    /// Returns [`Scheduler`] of the stage with specified name.
+    pub fn stage_mut(&mut self, name: &str) -> Option<&mut Scheduler> {
+        self.stages
+            .iter_mut()
+            .find(|stage| stage.name == name)
+            .map(|stage| &mut stage.scheduler)
+    }
+
+    /// Adds system to the stage with specified name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no stage with the specified name.
+    pub fn add_system<M, S>(&mut self, stage: &str, system: S) -> SystemConfig<'_>
+    where
+        S: IntoSystem<M>,
+    {
+        match self.stage_mut(stage) {
+            Some(scheduler) => scheduler.add_system(system),
+            None => panic!("Stage `{stage}` is not found in the schedule"),
+        }
+    }
+
+    /// Runs all stages using worker threads of the [`ThreadPool`].
+    ///
+    /// Stages without their own thread pool share one thread pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if schedule has fixed stages and [`Time`] resource is not present in the [`World`].
+    #[cfg(feature = "std")]
+    pub fn run_threaded(&mut self, world: &mut World) {
+        let mut pool = None::<Arc<ThreadPool>>;
+        for stage in &mut self.stages {
+            match (&stage.scheduler.thread_pool, &pool) {
+                (Some(_), _) => {}
+                (None, Some(pool)) => stage.scheduler.set_thread_pool(pool.clone()),
+                (None, None) => pool = Some(stage.scheduler.thread_pool().clone()),
+            }
+        }
+
+        self.run_stages(world, Scheduler::run_threaded);
+    }
+
+    /// Runs all stages using rayon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if schedule has fixed stages and [`Time`] resource is not present in the [`World`].
+    #[cfg(feature = "rayon")]
+    pub fn run_rayon(&mut self, world: &mut World) {
+        self.run_stages(world, Scheduler::run_rayon);
+    }
+
+    /// Runs all stages sequentially.
+    ///
+    /// # Panics
+    ///
+    /// Panics if schedule has fixed stages and [`Time`] resource is not present in the [`World`].
+    pub fn run_sequential(&mut self, world: &mut World) {
+        self.run_stages(world, Scheduler::run_sequential);
+    }
+
+    fn push_stage(&mut self, name: &'static str, fixed: Option<FixedStep>) -> &mut Scheduler {
+        assert!(
+            self.stages.iter().all(|stage| stage.name != name),
+            "Stage `{name}` already exists in the schedule"
+        );
+
+        self.stages.push(Stage {
+            name,
+            scheduler: Scheduler::new(),
+            fixed,
+        });
+        &mut self.stages.last_mut().unwrap().scheduler
+    }
+
+    fn run_stages(&mut self, world: &mut World, mut run: impl FnMut(&mut Scheduler, &mut World)) {
+        let max_fixed_steps = self.max_fixed_steps;
+
+        for stage in &mut self.stages {
+            match &mut stage.fixed {
+                None => run(&mut stage.scheduler, world),
+                Some(fixed) => {
+                    fixed.accumulator += world.expect_resource::<Time>().delta();
+
+                    let mut steps = 0;
+                    while fixed.accumulator >= fixed.step {
+                        if max_fixed_steps.is_some_and(|max| steps >= max) {
+                            // Drop skipped whole steps, keeping the phase.
+                            let rem = fixed.accumulator.as_nanos() % fixed.step.as_nanos();
+                            fixed.accumulator = Duration::from_nanos(rem as u64);
+                            break;
+                        }
+
+                        fixed.accumulator -= fixed.step;
+                        run(&mut stage.scheduler, world);
+                        steps += 1;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use alloc::boxed::Box;
+use core::{
+    any::{type_name, Any, TypeId},
+    fmt,
+    ptr::NonNull,
+};
+
+use crate::{archetype::Archetype, component::ComponentInfo, world::World, Access};
+
+use super::{ActionBufferQueue, PipeSystem, System};
+
+trait DynError: fmt::Debug + fmt::Display + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<E> DynError for E
+where
+    E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Error returned from fallible system.
+///
+/// Wraps any error type returned from function-system
+/// that returns `Result<(), E>`.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, resources::Res, system::{System, IntoSystem}};
+/// #[edict::system::system]
+/// fn parse(input: Res<&'static str>) -> Result<(), core::num::ParseIntError> {
+///     let _value: u32 = input.parse()?;
+///     Ok(())
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource("not a number");
+///
+/// let mut system = parse.into_system();
+/// let mut buffers = Vec::new();
+/// let error = unsafe { system.try_run_unchecked((&mut world).into(), &mut buffers) }.unwrap_err();
+/// assert!(error.downcast_ref::<core::num::ParseIntError>().is_some());
+/// ```
+///
+/// Only `Result<(), E>` makes function a fallible system,
+/// so [`system`] attribute rejects functions returning other `Result` types.
+///
+/// ```compile_fail
+/// #[edict::system::system]
+/// fn parse() -> Result<u32, core::num::ParseIntError> {
+///     "42".parse()
+/// }
+/// ```
+///
+/// [`system`]: crate::system::system
+pub struct SystemError {
+    error: Box<dyn DynError>,
+}
+
+impl SystemError {
+    /// Wraps error value.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        SystemError {
+            error: Box::new(error),
+        }
+    }
+
+    /// Returns reference to the wrapped error if it is of type `E`.
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        (*self.error).as_any().downcast_ref()
+    }
+}
+
+impl fmt::Debug for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.error, f)
+    }
+}
+
+impl fmt::Display for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SystemError {}
+
+/// Marker for [`IntoSystem`] for functions that return `Result<(), E>`.
+///
+/// [`IntoSystem`]: super::IntoSystem
+pub struct IsFallibleFunctionSystem<Args> {
+    marker: core::marker::PhantomData<fn(Args)>,
+}
+
+/// Wraps system that returns `Result<(), E>` and implements [`System`]
+/// that reports the error from [`System::try_run_unchecked`].
+pub struct FallibleSystem<S> {
+    system: S,
+}
+
+impl<S> FallibleSystem<S> {
+    /// Wraps the system.
+    pub fn new(system: S) -> Self {
+        FallibleSystem { system }
+    }
+}
+
+unsafe impl<S, E> System for FallibleSystem<S>
+where
+    S: PipeSystem<(), Out = Result<(), E>>,
+    E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        self.system.is_local()
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        self.system.world_access()
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.system.visit_archetype(archetype)
+    }
+
+    #[inline(always)]
+    fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+        self.system.component_access(archetype, comp)
+    }
+
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        self.system.resource_type_access(ty)
+    }
+
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        self.system.init(world);
+    }
+
+    #[inline(always)]
+    unsafe fn run_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) {
+        if let Err(err) = unsafe { self.try_run_unchecked(world, queue) } {
+            panic!("System `{}` failed: {err}", type_name::<S>());
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn try_run_unchecked(
+        &mut self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> Result<(), SystemError> {
+        unsafe { self.system.call_unchecked((), world, queue) }.map_err(SystemError::new)
+    }
+}
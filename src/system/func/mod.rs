@@ -12,11 +12,17 @@ use core::{
 
 use crate::{archetype::Archetype, component::ComponentInfo, world::World};
 
-use super::{Access, ActionBufferQueue, IntoSystem, System};
+use super::{
+    Access, ActionBufferQueue, Condition, FallibleSystem, In, IntoCondition, IntoPipeSystem,
+    IntoSystem, IsFallibleFunctionSystem, PipeSystem, System,
+};
 
 pub use self::{
     action::ActionEncoderState,
-    res::{ResLocal, ResMutLocal, ResMutNoSendState, ResMutState, ResNoSyncState, ResState},
+    res::{
+        OptionResMutState, OptionResState, ResInit, ResInitState, ResLocal, ResMutInit,
+        ResMutInitState, ResMutLocal, ResMutNoSendState, ResMutState, ResNoSyncState, ResState,
+    },
     state::{State, StateState},
     view::QueryArg,
 };
@@ -79,6 +85,14 @@ pub unsafe trait FnArgState: Send + 'static {
     #[must_use]
     fn resource_type_access(&self, ty: TypeId) -> Option<Access>;
 
+    /// Initializes the state using exclusive access to the [`World`].
+    /// Called before the system runs for the first time.
+    /// May be called more than once, so implementations should be idempotent.
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        let _ = world;
+    }
+
     /// Extracts argument from the world.
     /// This method is called with synchronization guarantees provided
     /// according to requirements returned by [`FnArgState::is_local`], [`FnArgState::world_access`],
@@ -118,30 +132,55 @@ pub struct FunctionSystem<F, ArgStates> {
     args: ArgStates,
 }
 
+/// Wrapper for function-like values that take [`In`] as first argument.
+///
+/// Implements [`PipeSystem`] and can run only as the receiving end of a [`Pipe`].
+///
+/// [`Pipe`]: super::Pipe
+pub struct InFunctionSystem<F, T, ArgStates> {
+    f: F,
+    args: ArgStates,
+    marker: PhantomData<fn(T)>,
+}
+
+/// Access declared by tuple of argument states of a function-system.
+trait FnArgStates {
+    fn is_local(&self) -> bool;
+    fn world_access(&self, name: &str) -> Option<Access>;
+    fn visit_archetype(&self, archetype: &Archetype) -> bool;
+    fn component_access(
+        &self,
+        archetype: &Archetype,
+        comp: &ComponentInfo,
+        name: &str,
+    ) -> Option<Access>;
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access>;
+    fn init(&mut self, world: &mut World);
+}
+
 macro_rules! impl_func {
     ($($a:ident)*) => {
         #[allow(unused_variables, unused_mut, non_snake_case)]
-        unsafe impl<Func $(,$a)*> System for FunctionSystem<Func, ($($a,)*)>
+        impl<$($a,)*> FnArgStates for ($($a,)*)
         where
             $($a: FnArgState,)*
-            Func: for<'a> FnMut($($a::Arg<'a>,)*),
         {
             #[inline(always)]
             fn is_local(&self) -> bool {
-                let ($($a,)*) = &self.args;
+                let ($($a,)*) = self;
                 false $( || $a.is_local() )*
             }
 
             #[inline(always)]
-            fn world_access(&self) -> Option<Access> {
-                let ($($a,)*) = &self.args;
+            fn world_access(&self, name: &str) -> Option<Access> {
+                let ($($a,)*) = self;
                 let mut result = None;
                 $(
                     result = match (result, $a.world_access()) {
                         (None, one) | (one, None) => one,
                         (Some(Access::Read), Some(Access::Read)) => Some(Access::Read),
                         _ => {
-                            panic!("Mutable `World` aliasing in system `{}`", type_name::<Self>());
+                            panic!("Mutable `World` aliasing in system `{}`", name);
                         }
                     };
                 )*
@@ -150,13 +189,13 @@ macro_rules! impl_func {
 
             #[inline(always)]
             fn visit_archetype(&self, archetype: &Archetype) -> bool {
-                let ($($a,)*) = &self.args;
+                let ($($a,)*) = self;
                 false $( || $a.visit_archetype(archetype) )*
             }
 
             #[inline(always)]
-            fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
-                let ($($a,)*) = &self.args;
+            fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo, name: &str) -> Option<Access> {
+                let ($($a,)*) = self;
                 let mut result = None;
                 let mut runtime_borrow = true;
                 $(
@@ -172,7 +211,7 @@ macro_rules! impl_func {
                                         // Conflict will be resolved at runtime.
                                         Some(Access::Write)
                                     } else {
-                                        panic!("Conflicting args in system `{}`.\nA component is aliased mutably.\nIf arguments require mutable aliasing, all arguments that access a type must use runtime borrow check.\nFor example `View` type does not use runtime borrow check and should be replaced with `ViewCell`.", type_name::<Func>());
+                                        panic!("Conflicting args in system `{}`.\nA component is aliased mutably.\nIf arguments require mutable aliasing, all arguments that access a type must use runtime borrow check.\nFor example `View` type does not use runtime borrow check and should be replaced with `ViewCell`.", name);
                                     }
                                 }
                             };
@@ -184,7 +223,7 @@ macro_rules! impl_func {
 
             #[inline(always)]
             fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
-                let ($($a,)*) = &self.args;
+                let ($($a,)*) = self;
                 let mut result = None;
                 $(
                     if let Some(access) = $a.resource_type_access(ty) {
@@ -202,21 +241,107 @@ macro_rules! impl_func {
                 result
             }
 
+            #[inline(always)]
+            fn init(&mut self, world: &mut World) {
+                let ($($a,)*) = self;
+                $( $a.init(world); )*
+            }
+        }
+
+        unsafe impl<Func, Ret $(,$a)*> System for FunctionSystem<Func, ($($a,)*)>
+        where
+            $($a: FnArgState,)*
+            Func: for<'a> FnMut($($a::Arg<'a>,)*) -> Ret,
+        {
+            #[inline(always)]
+            fn is_local(&self) -> bool {
+                self.args.is_local()
+            }
+
+            #[inline(always)]
+            fn world_access(&self) -> Option<Access> {
+                self.args.world_access(type_name::<Self>())
+            }
+
+            #[inline(always)]
+            fn visit_archetype(&self, archetype: &Archetype) -> bool {
+                self.args.visit_archetype(archetype)
+            }
+
+            #[inline(always)]
+            fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+                self.args.component_access(archetype, comp, type_name::<Func>())
+            }
+
+            #[inline(always)]
+            fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+                self.args.resource_type_access(ty)
+            }
+
+            #[inline(always)]
+            fn init(&mut self, world: &mut World) {
+                self.args.init(world);
+            }
+
             #[inline(always)]
             unsafe fn run_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) {
+                unsafe { PipeSystem::call_unchecked(self, (), world, queue); }
+            }
+        }
+
+        #[allow(unused_variables, unused_mut, non_snake_case)]
+        unsafe impl<Func, Ret $(,$a)*> PipeSystem<()> for FunctionSystem<Func, ($($a,)*)>
+        where
+            $($a: FnArgState,)*
+            Func: for<'a> FnMut($($a::Arg<'a>,)*) -> Ret,
+        {
+            type Out = Ret;
+
+            #[inline(always)]
+            unsafe fn call_unchecked(&mut self, _input: (), world: NonNull<World>, queue: &mut dyn ActionBufferQueue) -> Ret {
                 let ($($a,)*) = &mut self.args;
 
-                {
+                let ret = {
                     $(
                         let $a = unsafe { $a.get_unchecked(world, queue) };
                     )*
 
-                    (self.f)($($a,)*);
-                }
+                    (self.f)($($a,)*)
+                };
 
                 $(
                     unsafe { $a.flush_unchecked(world, queue) };
                 )*
+
+                ret
+            }
+        }
+
+        unsafe impl<Func $(,$a)*> Condition for FunctionSystem<Func, ($($a,)*)>
+        where
+            $($a: FnArgState,)*
+            Func: for<'a> FnMut($($a::Arg<'a>,)*) -> bool,
+        {
+            #[inline(always)]
+            unsafe fn check_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) -> bool {
+                unsafe { PipeSystem::call_unchecked(self, (), world, queue) }
+            }
+        }
+
+        impl<Func $(, $a)*> IntoCondition<IsFunctionSystem<($($a,)*)>> for Func
+        where
+            $($a: FnArg,)*
+            Func: FnMut($($a),*) -> bool + Send + 'static,
+            Func: for<'a> FnMut($(<$a::State as FnArgState>::Arg<'a>),*) -> bool,
+        {
+            type Condition = FunctionSystem<Self, ($($a::State,)*)>;
+
+            #[inline(always)]
+            fn into_condition(self) -> Self::Condition {
+                FunctionSystem {
+                    f: self,
+                    args: ($($a::State::new(),)*),
+                }
             }
         }
 
@@ -236,6 +361,129 @@ macro_rules! impl_func {
                 }
             }
         }
+
+        impl<Func, Error $(, $a)*> IntoSystem<IsFallibleFunctionSystem<($($a,)*)>> for Func
+        where
+            $($a: FnArg,)*
+            Error: core::fmt::Debug + core::fmt::Display + Send + Sync + 'static,
+            Func: FnMut($($a),*) -> Result<(), Error> + Send + 'static,
+            Func: for<'a> FnMut($(<$a::State as FnArgState>::Arg<'a>),*) -> Result<(), Error>,
+        {
+            type System = FallibleSystem<FunctionSystem<Self, ($($a::State,)*)>>;
+
+            #[inline(always)]
+            fn into_system(self) -> Self::System {
+                FallibleSystem::new(FunctionSystem {
+                    f: self,
+                    args: ($($a::State::new(),)*),
+                })
+            }
+        }
+
+        impl<Func, Ret $(, $a)*> IntoPipeSystem<(), IsFunctionSystem<($($a,)*)>> for Func
+        where
+            $($a: FnArg,)*
+            Func: FnMut($($a),*) -> Ret + Send + 'static,
+            Func: for<'a> FnMut($(<$a::State as FnArgState>::Arg<'a>),*) -> Ret,
+        {
+            type System = FunctionSystem<Self, ($($a::State,)*)>;
+
+            #[inline(always)]
+            fn into_pipe_system(self) -> Self::System {
+                FunctionSystem {
+                    f: self,
+                    args: ($($a::State::new(),)*),
+                }
+            }
+        }
+
+        unsafe impl<Func, T, Ret $(,$a)*> System for InFunctionSystem<Func, T, ($($a,)*)>
+        where
+            $($a: FnArgState,)*
+            Func: for<'a> FnMut(In<T>, $($a::Arg<'a>,)*) -> Ret,
+        {
+            #[inline(always)]
+            fn is_local(&self) -> bool {
+                self.args.is_local()
+            }
+
+            #[inline(always)]
+            fn world_access(&self) -> Option<Access> {
+                self.args.world_access(type_name::<Self>())
+            }
+
+            #[inline(always)]
+            fn visit_archetype(&self, archetype: &Archetype) -> bool {
+                self.args.visit_archetype(archetype)
+            }
+
+            #[inline(always)]
+            fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+                self.args.component_access(archetype, comp, type_name::<Func>())
+            }
+
+            #[inline(always)]
+            fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+                self.args.resource_type_access(ty)
+            }
+
+            #[inline(always)]
+            fn init(&mut self, world: &mut World) {
+                self.args.init(world);
+            }
+
+            #[inline(always)]
+            unsafe fn run_unchecked(&mut self, _world: NonNull<World>, _queue: &mut dyn ActionBufferQueue) {
+                panic!("System `{}` expects `In<{}>` argument and can run only as a part of a pipe", type_name::<Func>(), type_name::<T>());
+            }
+        }
+
+        #[allow(unused_variables, unused_mut, non_snake_case)]
+        unsafe impl<Func, T, Ret $(,$a)*> PipeSystem<T> for InFunctionSystem<Func, T, ($($a,)*)>
+        where
+            $($a: FnArgState,)*
+            Func: for<'a> FnMut(In<T>, $($a::Arg<'a>,)*) -> Ret,
+        {
+            type Out = Ret;
+
+            #[inline(always)]
+            unsafe fn call_unchecked(&mut self, input: T, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) -> Ret {
+                let ($($a,)*) = &mut self.args;
+
+                let ret = {
+                    $(
+                        let $a = unsafe { $a.get_unchecked(world, queue) };
+                    )*
+
+                    (self.f)(In(input), $($a,)*)
+                };
+
+                $(
+                    unsafe { $a.flush_unchecked(world, queue) };
+                )*
+
+                ret
+            }
+        }
+
+        impl<Func, T, Ret $(, $a)*> IntoPipeSystem<T, IsFunctionSystem<(In<T>, $($a,)*)>> for Func
+        where
+            T: 'static,
+            $($a: FnArg,)*
+            Func: FnMut(In<T>, $($a),*) -> Ret + Send + 'static,
+            Func: for<'a> FnMut(In<T>, $(<$a::State as FnArgState>::Arg<'a>),*) -> Ret,
+        {
+            type System = InFunctionSystem<Self, T, ($($a::State,)*)>;
+
+            #[inline(always)]
+            fn into_pipe_system(self) -> Self::System {
+                InFunctionSystem {
+                    f: self,
+                    args: ($($a::State::new(),)*),
+                    marker: PhantomData,
+                }
+            }
+        }
     }
 }
 
@@ -14,7 +14,7 @@ use crate::{
     world::World,
 };
 
-use super::{FnArg, FnArgState};
+use super::{FnArg, FnArgState, FromWorld};
 
 /// State for [`Res`] argument.
 pub struct ResState<T> {
@@ -424,6 +424,441 @@ where
     }
 }
 
+/// State for `Option<Res>` argument.
+pub struct OptionResState<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for OptionResState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> FnArg for Option<Res<'a, T>>
+where
+    T: Sync + 'static,
+{
+    type State = OptionResState<T>;
+}
+
+unsafe impl<T> FnArgState for OptionResState<T>
+where
+    T: Sync + 'static,
+{
+    type Arg<'a> = Option<Res<'a, T>>;
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Read)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn borrows_components_at_runtime(&self) -> bool {
+        false
+    }
+
+    /// Returns access type to the specified component type this argument may perform.
+    #[inline(always)]
+    fn component_access(&self, _comp: &ComponentInfo) -> Option<Access> {
+        None
+    }
+
+    /// Returns access type to the specified resource type this argument may perform.
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        if ty == type_id::<T>() {
+            Some(Access::Read)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked<'a>(
+        &'a mut self,
+        world: NonNull<World>,
+        _queue: &mut dyn ActionBufferQueue,
+    ) -> Option<Res<'a, T>> {
+        // Safety: Declares read access.
+        let world = unsafe { world.as_ref() };
+        world.get_resource()
+    }
+}
+
+/// State for `Option<ResMut>` argument.
+pub struct OptionResMutState<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for OptionResMutState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> FnArg for Option<ResMut<'a, T>>
+where
+    T: Send + 'static,
+{
+    type State = OptionResMutState<T>;
+}
+
+unsafe impl<T> FnArgState for OptionResMutState<T>
+where
+    T: Send + 'static,
+{
+    type Arg<'a> = Option<ResMut<'a, T>>;
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Read)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn borrows_components_at_runtime(&self) -> bool {
+        false
+    }
+
+    /// Returns access type to the specified component type this argument may perform.
+    #[inline(always)]
+    fn component_access(&self, _comp: &ComponentInfo) -> Option<Access> {
+        None
+    }
+
+    /// Returns access type to the specified resource type this argument may perform.
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        if ty == type_id::<T>() {
+            Some(Access::Write)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked<'a>(
+        &'a mut self,
+        world: NonNull<World>,
+        _queue: &mut dyn ActionBufferQueue,
+    ) -> Option<ResMut<'a, T>> {
+        // Safety: Declares read access.
+        let world = unsafe { world.as_ref() };
+        world.get_resource_mut()
+    }
+}
+
+/// Function-system argument to fetch resource immutably.
+/// Resource is created with [`FromWorld`] and inserted
+/// before the system runs for the first time if it is missing.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, scheduler::Scheduler, system::ResInit};
+/// #[derive(Default)]
+/// struct Config {
+///     speed: f32,
+/// }
+///
+/// let mut world = World::new();
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system(|config: ResInit<Config>| {
+///     assert_eq!(config.speed, 0.0);
+/// });
+///
+/// scheduler.run_sequential(&mut world);
+/// assert!(world.get_resource::<Config>().is_some());
+/// ```
+#[repr(transparent)]
+pub struct ResInit<'a, T: ?Sized> {
+    inner: Res<'a, T>,
+}
+
+impl<'a, T> Deref for ResInit<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.inner.deref()
+    }
+}
+
+impl<'a, T> ResInit<'a, T>
+where
+    T: ?Sized,
+{
+    /// Returns inner `Res` guard.
+    #[inline(always)]
+    pub fn inner(self) -> Res<'a, T> {
+        self.inner
+    }
+}
+
+/// State for [`ResInit`] argument.
+pub struct ResInitState<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for ResInitState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> FnArg for ResInit<'a, T>
+where
+    T: FromWorld + Send + Sync + 'static,
+{
+    type State = ResInitState<T>;
+}
+
+unsafe impl<T> FnArgState for ResInitState<T>
+where
+    T: FromWorld + Send + Sync + 'static,
+{
+    type Arg<'a> = ResInit<'a, T>;
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Read)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn borrows_components_at_runtime(&self) -> bool {
+        false
+    }
+
+    /// Returns access type to the specified component type this argument may perform.
+    #[inline(always)]
+    fn component_access(&self, _comp: &ComponentInfo) -> Option<Access> {
+        None
+    }
+
+    /// Returns access type to the specified resource type this argument may perform.
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        if ty == type_id::<T>() {
+            Some(Access::Read)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        init_resource::<T>(world);
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked<'a>(
+        &'a mut self,
+        world: NonNull<World>,
+        _queue: &mut dyn ActionBufferQueue,
+    ) -> ResInit<'a, T> {
+        // Safety: Declares read access.
+        let world = unsafe { world.as_ref() };
+        match world.get_resource() {
+            Some(r) => ResInit { inner: r },
+            None => missing_resource::<T>(),
+        }
+    }
+}
+
+/// Function-system argument to fetch resource mutably.
+/// Resource is created with [`FromWorld`] and inserted
+/// before the system runs for the first time if it is missing.
+#[repr(transparent)]
+pub struct ResMutInit<'a, T: ?Sized> {
+    inner: ResMut<'a, T>,
+}
+
+impl<'a, T> Deref for ResMutInit<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.inner.deref()
+    }
+}
+
+impl<'a, T> DerefMut for ResMutInit<'a, T>
+where
+    T: ?Sized,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.deref_mut()
+    }
+}
+
+impl<'a, T> ResMutInit<'a, T>
+where
+    T: ?Sized,
+{
+    /// Returns inner `ResMut` guard.
+    #[inline(always)]
+    pub fn inner(self) -> ResMut<'a, T> {
+        self.inner
+    }
+}
+
+/// State for [`ResMutInit`] argument.
+pub struct ResMutInitState<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for ResMutInitState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> FnArg for ResMutInit<'a, T>
+where
+    T: FromWorld + Send + 'static,
+{
+    type State = ResMutInitState<T>;
+}
+
+unsafe impl<T> FnArgState for ResMutInitState<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    type Arg<'a> = ResMutInit<'a, T>;
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Read)
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn borrows_components_at_runtime(&self) -> bool {
+        false
+    }
+
+    /// Returns access type to the specified component type this argument may perform.
+    #[inline(always)]
+    fn component_access(&self, _comp: &ComponentInfo) -> Option<Access> {
+        None
+    }
+
+    /// Returns access type to the specified resource type this argument may perform.
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        if ty == type_id::<T>() {
+            Some(Access::Write)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        init_resource::<T>(world);
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked<'a>(
+        &'a mut self,
+        world: NonNull<World>,
+        _queue: &mut dyn ActionBufferQueue,
+    ) -> ResMutInit<'a, T> {
+        // Safety: Declares read access.
+        let world = unsafe { world.as_ref() };
+        match world.get_resource_mut() {
+            Some(r) => ResMutInit { inner: r },
+            None => missing_resource::<T>(),
+        }
+    }
+}
+
+/// Inserts resource created with [`FromWorld`] if it is missing.
+fn init_resource<T: FromWorld + 'static>(world: &mut World) {
+    if !world.resource_types().any(|ty| ty == type_id::<T>()) {
+        let resource = T::from_world(world);
+        world.insert_resource(resource);
+    }
+}
+
 fn missing_resource<T>() -> ! {
     panic!("Missing resource '{}'", type_name::<T>())
 }
@@ -9,7 +9,10 @@ use crate::{
     component::ComponentInfo,
     query::SendQuery,
     system::ActionBufferQueue,
-    view::{NonExtensible, RuntimeBorrowState, StaticallyBorrowed, View, ViewCell, ViewValue},
+    view::{
+        ArchetypeCache, NonExtensible, RuntimeBorrowState, StaticallyBorrowed, View, ViewCell,
+        ViewValue,
+    },
     world::World,
     Access,
 };
@@ -20,6 +23,13 @@ use crate::view::{acquire, release};
 use super::{FnArg, FnArgState};
 
 /// Query suitable for [`View`] args for function-systems.
+///
+/// Views in function-systems cache archetypes matched by the query
+/// until archetype set changes.
+/// So result of [`Query::visit_archetype`] must depend only on the archetype
+/// and not on the query state, otherwise newly matching archetypes may be skipped.
+///
+/// [`Query::visit_archetype`]: crate::query::Query::visit_archetype
 #[diagnostic::on_unimplemented(
     label = "`{Self}` cannot be used as query in function-system argument"
 )]
@@ -45,6 +55,7 @@ pub trait QueryArg: SendQuery {
 pub struct ViewState<Q, F, B> {
     query: Q,
     filter: F,
+    cache: ArchetypeCache,
     marker: PhantomData<B>,
 }
 
@@ -68,6 +79,7 @@ where
         ViewState {
             query: Q::new(),
             filter: F::new(),
+            cache: ArchetypeCache::default(),
             marker: PhantomData,
         }
     }
@@ -129,7 +141,10 @@ where
         self.query.before(world);
         self.filter.before(world);
 
+        let matches = self.cache.update(world, self.query, self.filter);
+
         // Safety: Declares access for these queries.
+        // Matches are computed for this world.
         unsafe {
             ViewValue::new_unchecked(
                 world,
@@ -138,6 +153,7 @@ where
                 RuntimeBorrowState::new(),
                 NonExtensible,
             )
+            .with_matches(matches)
         }
     }
 
@@ -174,6 +190,7 @@ where
         ViewState {
             query: Q::new(),
             filter: F::new(),
+            cache: ArchetypeCache::default(),
             marker: PhantomData,
         }
     }
@@ -236,7 +253,10 @@ where
         self.query.before(world);
         self.filter.before(world);
 
+        let matches = self.cache.update(world, self.query, self.filter);
+
         // Safety: Declares access for these queries.
+        // Matches are computed for this world.
         unsafe {
             ViewValue::new_unchecked(
                 world,
@@ -245,6 +265,7 @@ where
                 StaticallyBorrowed,
                 NonExtensible,
             )
+            .with_matches(matches)
         }
     }
 
@@ -1,6 +1,8 @@
 //! Provides API to define systems compatible with built-in scheduler.
 
+mod error;
 mod func;
+mod pipe;
 
 use alloc::vec::Vec;
 use core::{any::TypeId, ptr::NonNull};
@@ -13,11 +15,19 @@ use crate::{
     Access,
 };
 
-pub use self::func::{
-    ActionEncoderState, FnArg, FnArgState, FromWorld, IsFunctionSystem, QueryArg, ResLocal,
-    ResMutLocal, ResMutNoSendState, ResMutState, ResNoSyncState, ResState, State, StateState,
+pub use self::{
+    error::{FallibleSystem, IsFallibleFunctionSystem, SystemError},
+    func::{
+        ActionEncoderState, FnArg, FnArgState, FromWorld, FunctionSystem, InFunctionSystem,
+        IsFunctionSystem, OptionResMutState, OptionResState, QueryArg, ResInit, ResInitState,
+        ResLocal, ResMutInit, ResMutInitState, ResMutLocal, ResMutNoSendState, ResMutState,
+        ResNoSyncState, ResState, State, StateState,
+    },
+    pipe::{In, IntoPipeSystem, Pipe, PipeOutput, PipeSystem, Piped},
 };
 
+pub(crate) use self::pipe::union;
+
 pub use edict_proc::system;
 
 /// A queue of `ActionEncoder` instances.
@@ -79,13 +89,41 @@ pub unsafe trait System {
     #[must_use]
     fn resource_type_access(&self, ty: TypeId) -> Option<Access>;
 
+    /// Initializes the system using exclusive access to the [`World`].
+    /// Called before the system runs for the first time.
+    /// May be called more than once, so implementations should be idempotent.
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        let _ = world;
+    }
+
     /// Runs the system with given context instance.
     ///
     /// If `is_local()` returns `true` then running it outside local thread is unsound.
     unsafe fn run_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue);
 
+    /// Runs the system with given context instance and returns error if system fails.
+    ///
+    /// Infallible systems always return `Ok(())`.
+    /// Fallible systems panic on error when run with [`System::run_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`System::run_unchecked`].
+    /// If `is_local()` returns `true` then running it outside local thread is unsound.
+    #[inline(always)]
+    unsafe fn try_run_unchecked(
+        &mut self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> Result<(), SystemError> {
+        unsafe { self.run_unchecked(world, queue) };
+        Ok(())
+    }
+
     /// Runs the system with exclusive access to [`World`].
     fn run(&mut self, world: &mut World, queue: &mut dyn ActionBufferQueue) {
+        self.init(world);
         unsafe { self.run_unchecked(NonNull::from(world), queue) };
     }
 
@@ -97,6 +135,52 @@ pub unsafe trait System {
     }
 }
 
+/// Run condition for a system.
+///
+/// Condition is a read-only system that returns `bool`.
+/// System with conditions runs only if all of them return `true`.
+///
+/// # Safety
+///
+/// [`Condition::check_unchecked`] has the same safety requirements as [`System::run_unchecked`].
+pub unsafe trait Condition: System {
+    /// Runs the condition with given context instance and returns its verdict.
+    ///
+    /// # Safety
+    ///
+    /// If `is_local()` returns `true` then running it outside local thread is unsound.
+    unsafe fn check_unchecked(
+        &mut self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> bool;
+}
+
+/// Trait for types that can be converted into run conditions.
+#[diagnostic::on_unimplemented(
+    message = "Type must be convertible into a run condition",
+    note = "If this is a function ensure that it returns `bool` and all arguments implement `FnArg`"
+)]
+pub trait IntoCondition<Marker> {
+    /// Type of the condition a value of this type can be converted into.
+    type Condition: Condition + Send + 'static;
+
+    /// Converts value into condition.
+    #[must_use]
+    fn into_condition(self) -> Self::Condition;
+}
+
+impl<T> IntoCondition<IsSystem> for T
+where
+    T: Condition + Send + 'static,
+{
+    type Condition = T;
+
+    fn into_condition(self) -> T {
+        self
+    }
+}
+
 /// Trait for types that can be converted into systems.
 #[diagnostic::on_unimplemented(
     message = "Type must be convertible into a system",
@@ -266,6 +350,12 @@ macro_rules! impl_system {
                 result
             }
 
+            #[inline]
+            fn init(&mut self, world: &mut World) {
+                let ($($a,)+) = &mut self.0;
+                $($a.init(world);)+
+            }
+
             /// Runs the system with given context instance.
             ///
             /// If `is_local()` returns `true` then running it outside local thread is unsound.
@@ -276,6 +366,16 @@ macro_rules! impl_system {
                     $($a.run_unchecked(world, queue);)+
                 }
             }
+
+            /// Runs systems in sequence, stopping on the first error.
+            #[inline]
+            unsafe fn try_run_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) -> Result<(), SystemError> {
+                let ($($a,)+) = &mut self.0;
+                unsafe {
+                    $($a.try_run_unchecked(world, queue)?;)+
+                }
+                Ok(())
+            }
         }
     };
 }
@@ -0,0 +1,386 @@
+use core::{
+    any::{type_name, TypeId},
+    fmt,
+    ptr::NonNull,
+};
+
+use crate::{archetype::Archetype, component::ComponentInfo, world::World, Access};
+
+use super::{ActionBufferQueue, IsSystem, System, SystemError};
+
+/// Argument of a function-system that receives output of another system.
+///
+/// Must be the first argument of the function.
+/// Such function can be used only as the receiving end of [`IntoPipeSystem::pipe`].
+///
+/// # Example
+///
+/// ```
+/// # use edict::{world::World, resources::ResMut, scheduler::Scheduler, system::{In, IntoPipeSystem}};
+/// let mut world = World::new();
+/// world.insert_resource(0u32);
+///
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system((|| 42u32).pipe(|In(value): In<u32>, mut res: ResMut<u32>| {
+///     *res = value;
+/// }));
+///
+/// scheduler.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<u32>(), 42);
+/// ```
+///
+/// Function that returns a value other than `Result`
+/// can be checked with [`system`] attribute to be a source of a pipe.
+///
+/// ```
+/// # use edict::{world::World, resources::{Res, ResMut}, scheduler::Scheduler, system::{In, IntoPipeSystem}};
+/// #[edict::system::system]
+/// fn source(value: Res<u32>) -> u32 {
+///     *value + 1
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(1u32);
+///
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_system(source.pipe(|In(value): In<u32>, mut res: ResMut<u32>| *res = value));
+/// scheduler.run_sequential(&mut world);
+/// assert_eq!(*world.expect_resource::<u32>(), 2);
+/// ```
+///
+/// [`system`]: crate::system::system
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct In<T>(pub T);
+
+/// System that receives input value and returns output value.
+///
+/// # Safety
+///
+/// [`PipeSystem::call_unchecked`] has the same safety requirements as [`System::run_unchecked`].
+pub unsafe trait PipeSystem<In>: System {
+    /// Output value of the system.
+    type Out;
+
+    /// Runs the system with given input and returns its output.
+    ///
+    /// # Safety
+    ///
+    /// If `is_local()` returns `true` then running it outside local thread is unsound.
+    unsafe fn call_unchecked(
+        &mut self,
+        input: In,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> Self::Out;
+}
+
+/// Trait for types that can be converted into systems with input and output.
+#[diagnostic::on_unimplemented(
+    message = "Type must be convertible into a pipe system",
+    note = "If this is a function ensure that all arguments implement `FnArg`, except optional `In<T>` first argument"
+)]
+pub trait IntoPipeSystem<In, Marker>: Sized {
+    /// Type of the system a value of this type can be converted into.
+    type System: PipeSystem<In> + Send + 'static;
+
+    /// Converts value into pipe system.
+    #[must_use]
+    fn into_pipe_system(self) -> Self::System;
+
+    /// Connects this system to another one that receives its output
+    /// as [`In`] argument.
+    ///
+    /// Systems run one after another as a single system,
+    /// with access being union of both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, resources::Res, scheduler::Scheduler, system::{In, IntoPipeSystem}};
+    /// fn parse(input: Res<&'static str>) -> Result<u32, core::num::ParseIntError> {
+    ///     input.parse()
+    /// }
+    ///
+    /// fn log_error(In(result): In<Result<u32, core::num::ParseIntError>>) {
+    ///     if let Err(err) = result {
+    ///         println!("Failed to parse: {err}");
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource("not a number");
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.add_system(parse.pipe(log_error));
+    /// scheduler.run_sequential(&mut world);
+    /// ```
+    #[must_use]
+    fn pipe<M, S>(self, other: S) -> Pipe<Self::System, S::System>
+    where
+        S: IntoPipeSystem<<Self::System as PipeSystem<In>>::Out, M>,
+    {
+        Pipe {
+            inner: Piped {
+                a: self.into_pipe_system(),
+                b: other.into_pipe_system(),
+            },
+        }
+    }
+}
+
+impl<T, In> IntoPipeSystem<In, IsSystem> for T
+where
+    T: PipeSystem<In> + Send + 'static,
+{
+    type System = T;
+
+    fn into_pipe_system(self) -> T {
+        self
+    }
+}
+
+/// Output of the last system in a [`Pipe`] that runs as a [`System`].
+///
+/// Implemented for `()` and `Result<(), E>`.
+/// Errors are reported from [`System::try_run_unchecked`]
+/// the same way as for fallible function-systems.
+pub trait PipeOutput {
+    /// Converts output into result of the system run.
+    fn into_result(self) -> Result<(), SystemError>;
+}
+
+impl PipeOutput for () {
+    #[inline(always)]
+    fn into_result(self) -> Result<(), SystemError> {
+        Ok(())
+    }
+}
+
+impl<E> PipeOutput for Result<(), E>
+where
+    E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    #[inline(always)]
+    fn into_result(self) -> Result<(), SystemError> {
+        self.map_err(SystemError::new)
+    }
+}
+
+/// System that runs two systems one after another,
+/// passing output of the first one to the second.
+///
+/// Created by [`IntoPipeSystem::pipe`].
+/// Can be used as [`System`] if output of the last system is [`PipeOutput`].
+pub struct Pipe<A, B> {
+    inner: Piped<A, B>,
+}
+
+/// Two systems connected within a [`Pipe`] after its first system.
+///
+/// Receives input for the first system and can run only as a part of a [`Pipe`].
+pub struct Piped<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Pipe<A, B>
+where
+    A: PipeSystem<()>,
+    B: PipeSystem<A::Out>,
+{
+    /// Connects this pipe to another system that receives its output
+    /// as [`In`] argument.
+    ///
+    /// Unlike [`IntoPipeSystem::pipe`] this does not require
+    /// output of this pipe to be [`PipeOutput`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, resources::ResMut, scheduler::Scheduler, system::{In, IntoPipeSystem}};
+    /// let mut world = World::new();
+    /// world.insert_resource(0u32);
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.add_system(
+    ///     (|| 20u32)
+    ///         .pipe(|In(value): In<u32>| value + 1)
+    ///         .pipe(|In(value): In<u32>, mut res: ResMut<u32>| *res = value * 2),
+    /// );
+    ///
+    /// scheduler.run_sequential(&mut world);
+    /// assert_eq!(*world.expect_resource::<u32>(), 42);
+    /// ```
+    #[must_use]
+    pub fn pipe<M, S>(self, other: S) -> Pipe<A, Piped<B, S::System>>
+    where
+        S: IntoPipeSystem<B::Out, M>,
+    {
+        Pipe {
+            inner: Piped {
+                a: self.inner.a,
+                b: Piped {
+                    a: self.inner.b,
+                    b: other.into_pipe_system(),
+                },
+            },
+        }
+    }
+}
+
+unsafe impl<A, B> System for Pipe<A, B>
+where
+    A: PipeSystem<()>,
+    B: PipeSystem<A::Out>,
+    B::Out: PipeOutput,
+{
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        self.inner.is_local()
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        self.inner.world_access()
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.inner.visit_archetype(archetype)
+    }
+
+    #[inline(always)]
+    fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+        self.inner.component_access(archetype, comp)
+    }
+
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        self.inner.resource_type_access(ty)
+    }
+
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        self.inner.init(world);
+    }
+
+    #[inline(always)]
+    unsafe fn run_unchecked(&mut self, world: NonNull<World>, queue: &mut dyn ActionBufferQueue) {
+        if let Err(err) = unsafe { self.try_run_unchecked(world, queue) } {
+            panic!("System `{}` failed: {err}", type_name::<B>());
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn try_run_unchecked(
+        &mut self,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> Result<(), SystemError> {
+        unsafe { self.inner.call_unchecked((), world, queue) }.into_result()
+    }
+}
+
+unsafe impl<A, B> PipeSystem<()> for Pipe<A, B>
+where
+    A: PipeSystem<()>,
+    B: PipeSystem<A::Out>,
+    B::Out: PipeOutput,
+{
+    type Out = B::Out;
+
+    #[inline(always)]
+    unsafe fn call_unchecked(
+        &mut self,
+        input: (),
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> B::Out {
+        unsafe { self.inner.call_unchecked(input, world, queue) }
+    }
+}
+
+unsafe impl<A, B> System for Piped<A, B>
+where
+    A: System,
+    B: System,
+{
+    #[inline(always)]
+    fn is_local(&self) -> bool {
+        self.a.is_local() || self.b.is_local()
+    }
+
+    #[inline(always)]
+    fn world_access(&self) -> Option<Access> {
+        union(self.a.world_access(), self.b.world_access())
+    }
+
+    #[inline(always)]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.a.visit_archetype(archetype) || self.b.visit_archetype(archetype)
+    }
+
+    #[inline(always)]
+    fn component_access(&self, archetype: &Archetype, comp: &ComponentInfo) -> Option<Access> {
+        let a = match self.a.visit_archetype(archetype) {
+            true => self.a.component_access(archetype, comp),
+            false => None,
+        };
+        let b = match self.b.visit_archetype(archetype) {
+            true => self.b.component_access(archetype, comp),
+            false => None,
+        };
+        union(a, b)
+    }
+
+    #[inline(always)]
+    fn resource_type_access(&self, ty: TypeId) -> Option<Access> {
+        union(
+            self.a.resource_type_access(ty),
+            self.b.resource_type_access(ty),
+        )
+    }
+
+    #[inline(always)]
+    fn init(&mut self, world: &mut World) {
+        self.a.init(world);
+        self.b.init(world);
+    }
+
+    #[inline(always)]
+    unsafe fn run_unchecked(&mut self, _world: NonNull<World>, _queue: &mut dyn ActionBufferQueue) {
+        panic!(
+            "System `{}` can run only as a part of a pipe",
+            type_name::<Self>()
+        );
+    }
+}
+
+unsafe impl<T, A, B> PipeSystem<T> for Piped<A, B>
+where
+    A: PipeSystem<T>,
+    B: PipeSystem<A::Out>,
+{
+    type Out = B::Out;
+
+    #[inline(always)]
+    unsafe fn call_unchecked(
+        &mut self,
+        input: T,
+        world: NonNull<World>,
+        queue: &mut dyn ActionBufferQueue,
+    ) -> B::Out {
+        let out = unsafe { self.a.call_unchecked(input, world, queue) };
+        unsafe { self.b.call_unchecked(out, world, queue) }
+    }
+}
+
+/// Returns access that covers both accesses.
+#[inline(always)]
+pub(crate) fn union(lhs: Option<Access>, rhs: Option<Access>) -> Option<Access> {
+    match (lhs, rhs) {
+        (Some(Access::Write), _) | (_, Some(Access::Write)) => Some(Access::Write),
+        (Some(Access::Read), _) | (_, Some(Access::Read)) => Some(Access::Read),
+        (None, None) => None,
+    }
+}
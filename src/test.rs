@@ -1,6 +1,7 @@
 use alloc::{vec, vec::Vec};
 
 use crate::{
+    action::ActionBuffer,
     component::Component,
     query::{Entities, ImmutableQuery, Modified, Not, With, Without},
     relation::{ChildOf, Relation},
@@ -574,6 +575,99 @@ fn insert_relation() {
     world.insert_relation(origin, ChildOf, target).unwrap();
 }
 
+#[test]
+fn spawn_with_children() {
+    let mut world = World::new();
+    let mut buffer = ActionBuffer::new();
+
+    let mut encoder = buffer.encoder(&world);
+    let root = encoder
+        .spawn_with_children((U32(0),), |c| {
+            c.spawn((U32(1),));
+            c.spawn_with_children((U32(2),), |c| {
+                c.spawn_one(U32(3));
+            });
+        })
+        .id();
+
+    buffer.execute(&mut world);
+
+    let children = world
+        .view::<Entities>()
+        .relates_to::<ChildOf>(root)
+        .iter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    assert_eq!(children.len(), 2);
+
+    world.despawn(root).unwrap();
+    assert_eq!(world.view::<&U32>().iter().count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn bounded_action_channel() {
+    let mut world = World::builder().with_action_channel_capacity(2).build();
+
+    let sender = world.new_action_sender();
+    let handle = std::thread::spawn(move || {
+        for i in 0..10 {
+            sender.spawn_blocking((U32(i),));
+        }
+    });
+
+    while !handle.is_finished() {
+        world.execute_received_actions();
+    }
+    handle.join().unwrap();
+    world.execute_received_actions();
+
+    let stats = world.action_channel_stats();
+    assert_eq!(stats.pending, 0);
+    assert_eq!(stats.executed, 10);
+    assert_eq!(stats.dropped, 0);
+    assert_eq!(world.view::<&U32>().iter().count(), 10);
+}
+
+/// Tests queries sent to full bounded channel.
+#[cfg(feature = "std")]
+#[test]
+fn bounded_action_channel_query() {
+    use crate::action::TrySendError;
+
+    let mut world = World::builder().with_action_channel_capacity(1).build();
+
+    let sender = world.new_action_sender();
+    sender.closure(|_| {});
+
+    // Dropped query resolves immediately.
+    assert_eq!(sender.query(|_| 1).wait(), None);
+    assert!(matches!(
+        sender.try_query(|_| 2),
+        Err(TrySendError::Full(_))
+    ));
+
+    world.execute_received_actions();
+    let response = sender.try_query(|_| 3).unwrap();
+    world.execute_received_actions();
+    assert_eq!(response.wait(), Some(3));
+    assert_eq!(world.action_channel_stats().dropped, 1);
+}
+
+/// Tests that batch spawning does not take capacity of bounded channel for internal actions.
+#[test]
+fn bounded_action_channel_batch() {
+    // Bundle registration and three spawns.
+    let mut world = World::builder().with_action_channel_capacity(4).build();
+
+    let sender = world.new_action_sender();
+    sender.spawn_batch((0..3).map(|i| (U32(i),))).spawn_all();
+    world.execute_received_actions();
+
+    assert_eq!(world.action_channel_stats().dropped, 0);
+    assert_eq!(world.view::<&U32>().iter().count(), 3);
+}
+
 #[cfg(feature = "flow")]
 #[test]
 fn test_flow() {
@@ -618,3 +712,526 @@ fn test_aliasing_borrows() {
 
     system.into_system().run_alone(&mut world);
 }
+
+/// Tests that parallel iteration visits every entity once
+/// and keeps change detection intact.
+#[test]
+#[cfg(feature = "std")]
+fn view_par_for_each() {
+    use crate::{
+        archetype::CHUNK_LEN,
+        query::{Read, Write},
+    };
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut world = World::new();
+
+    let count = CHUNK_LEN * 3 + 5;
+    let mut entities = Vec::new();
+    for i in 0..count {
+        entities.push(world.spawn((U32(i),)).id());
+        world.spawn((U32(i), Bool(false)));
+    }
+
+    let after = world.epoch();
+    world
+        .view_mut::<&mut U32>()
+        .par_for_each(|value| value.0 *= 2);
+
+    let sum: u32 = world.view::<&U32>().into_iter().map(|value| value.0).sum();
+    assert_eq!(sum, count * (count - 1) * 2);
+
+    let modified = AtomicUsize::new(0);
+    world
+        .view_with_mut(Modified::<Read<U32>>::new(after))
+        .par_for_each(|_| {
+            modified.fetch_add(1, Ordering::Relaxed);
+        });
+    assert_eq!(modified.load(Ordering::Relaxed), count as usize * 2);
+
+    let after = world.epoch();
+    world
+        .get::<&mut U32>(entities[CHUNK_LEN as usize + 1])
+        .unwrap()
+        .0 = 0;
+
+    let modified = AtomicUsize::new(0);
+    world
+        .view_with_mut(Modified::<Write<U32>>::new(after))
+        .par_for_each(|value| {
+            assert_eq!(value.0, 0);
+            modified.fetch_add(1, Ordering::Relaxed);
+        });
+    assert_eq!(modified.load(Ordering::Relaxed), 1);
+}
+
+/// Tests that views in systems see archetypes created between runs.
+#[test]
+fn system_view_new_archetypes() {
+    let mut world = World::new();
+    world.insert_resource(0usize);
+
+    world.spawn((U32(1),));
+
+    let mut system = (|view: View<&U32, Without<Bool>>,
+                       mut count: crate::resources::ResMut<usize>| {
+        *count = view.iter().count();
+    })
+    .into_system();
+
+    system.run_alone(&mut world);
+    assert_eq!(*world.expect_resource::<usize>(), 1);
+
+    world.spawn((U32(2), Str("new")));
+    world.spawn((U32(3), Bool(true)));
+
+    system.run_alone(&mut world);
+    assert_eq!(*world.expect_resource::<usize>(), 2);
+
+    system.run_alone(&mut world);
+    assert_eq!(*world.expect_resource::<usize>(), 2);
+}
+
+#[derive(crate::query::Query)]
+struct Mover<'a> {
+    value: &'a mut U32,
+    name: Option<&'a Str>,
+    entity: crate::entity::EntityLoc<'a>,
+}
+
+#[derive(crate::query::Query)]
+struct Names<'a>(&'a Str, crate::entity::EntityLoc<'a>);
+
+/// Tests query derived for a struct.
+#[test]
+fn derive_query() {
+    let mut world = World::new();
+
+    let a = world.spawn((U32(1), Str("a"))).id();
+    let b = world.spawn((U32(2),)).id();
+    world.spawn((Str("c"),));
+
+    for Mover {
+        value,
+        name,
+        entity,
+    } in world.view_mut::<Mover>()
+    {
+        value.0 *= 10;
+        if entity.id() == a {
+            assert_eq!(name, Some(&Str("a")));
+        } else {
+            assert_eq!(entity.id(), b);
+            assert_eq!(name, None);
+        }
+    }
+
+    assert_eq!(world.get::<&U32>(a).unwrap().0, 10);
+    assert_eq!(world.get::<&U32>(b).unwrap().0, 20);
+
+    let e = world.entity(a).unwrap();
+    let Names(name, entity): Names = e.get::<Names>().unwrap();
+    assert_eq!(name, &Str("a"));
+    assert_eq!(entity.id(), a);
+
+    world.insert_resource(0u32);
+    let mut system = (|mut view: View<Mover>, mut sum: crate::resources::ResMut<u32>| {
+        *sum = view.iter_mut().map(|m| m.value.0).sum();
+    })
+    .into_system();
+    system.run_alone(&mut world);
+    assert_eq!(*world.expect_resource::<u32>(), 30);
+}
+
+/// Tests `AnyOf` query and its `Modified` variant.
+#[test]
+fn any_of_query() {
+    use crate::{query::AnyOf, resources::ResMut};
+
+    let mut world = World::new();
+    world.spawn((U32(1),));
+    let b = world.spawn((Str("b"),)).id();
+    world.spawn((U32(3), Str("c")));
+    world.spawn((Bool(true),));
+
+    let mut items = world
+        .view_mut::<AnyOf<(&mut U32, &Str)>>()
+        .into_iter()
+        .map(|(value, name)| (value.map(|value| value.0), name.copied()))
+        .collect::<Vec<_>>();
+    items.sort_by_key(|(value, _)| *value);
+
+    assert_eq!(
+        items,
+        vec![
+            (None, Some(Str("b"))),
+            (Some(1), None),
+            (Some(3), Some(Str("c"))),
+        ]
+    );
+
+    world.insert_resource(Vec::<(bool, bool)>::new());
+    let mut system = (|view: View<Modified<AnyOf<(&U32, &Str)>>>,
+                       mut seen: ResMut<Vec<(bool, bool)>>| {
+        seen.clear();
+        seen.extend(view.iter().map(|(a, b)| (a.is_some(), b.is_some())));
+    })
+    .into_system();
+
+    system.run_alone(&mut world);
+    assert_eq!(world.expect_resource::<Vec<(bool, bool)>>().len(), 3);
+
+    system.run_alone(&mut world);
+    assert!(world.expect_resource::<Vec<(bool, bool)>>().is_empty());
+
+    world.get::<&mut Str>(b).unwrap().0 = "bb";
+    system.run_alone(&mut world);
+    assert_eq!(
+        *world.expect_resource::<Vec<(bool, bool)>>(),
+        vec![(false, true)]
+    );
+}
+
+/// Tests that index follows all kinds of component changes.
+#[test]
+fn index_sync() {
+    use crate::index::{HashIndex, Index};
+
+    struct ByStr;
+
+    impl Index for ByStr {
+        type Component = Str;
+        type Key = &'static str;
+        type Storage = HashIndex<&'static str>;
+
+        fn key(s: &Str) -> &'static str {
+            s.0
+        }
+    }
+
+    let mut world = World::new();
+    let a = world.spawn((Str("a"),)).id();
+
+    // Entities spawned before registration are indexed too.
+    world.register_index::<ByStr>();
+    assert_eq!(world.index::<ByStr>().get("a"), [a]);
+
+    let b = world.spawn((Str("b"), U32(0))).id();
+    let c = world.spawn((Str("c"),)).id();
+    assert_eq!(world.index::<ByStr>().len(), 3);
+
+    // Modified through a view.
+    for s in world.view_mut::<&mut Str>().iter_mut() {
+        if s.0 == "c" {
+            s.0 = "a";
+        }
+    }
+    let mut ids = world.index::<ByStr>().get("a").to_vec();
+    ids.sort();
+    assert_eq!(ids, [a, c]);
+    assert!(world.index::<ByStr>().get("c").is_empty());
+
+    // Replaced.
+    world.insert(a, Str("x")).unwrap();
+    assert_eq!(world.index::<ByStr>().get("x"), [a]);
+    assert_eq!(world.index::<ByStr>().key_of(a), Some(&"x"));
+
+    // Taken out, dropped and despawned.
+    world.remove::<Str>(a).unwrap();
+    world.drop::<Str>(b).unwrap();
+    world.despawn(c).unwrap();
+    assert!(world.index::<ByStr>().is_empty());
+    assert!(!world.index::<ByStr>().contains("a"));
+
+    // Inserted into existing entity.
+    world.insert(b, Str("b")).unwrap();
+    assert_eq!(world.index::<ByStr>().get("b"), [b]);
+}
+
+/// Tests query composed at runtime.
+#[test]
+fn dynamic_query() {
+    use core::any::TypeId;
+
+    use crate::{
+        component::ComponentInfo,
+        query::{DynamicQuery, DynamicTerm, Query, Read},
+        Access,
+    };
+
+    let mut world = World::new();
+    world.spawn((U32(1), Str("a")));
+    world.spawn((U32(2),));
+    world.spawn((U32(3), Str("c"), Bool(true)));
+
+    let query = DynamicQuery::new([
+        DynamicTerm::write::<U32>(),
+        DynamicTerm::with(TypeId::of::<Str>()),
+        DynamicTerm::without(TypeId::of::<Bool>()),
+    ]);
+
+    assert_eq!(
+        query.component_access(&ComponentInfo::of::<U32>()).ok(),
+        Some(Some(Access::Write))
+    );
+    assert_eq!(
+        query.component_access(&ComponentInfo::of::<Str>()).ok(),
+        Some(None)
+    );
+
+    let alias = DynamicQuery::new([DynamicTerm::read::<U32>(), DynamicTerm::write::<U32>()]);
+    assert!(alias.component_access(&ComponentInfo::of::<U32>()).is_err());
+
+    let before = world.epoch();
+
+    let mut count = 0;
+    for item in world.view_with_mut(query) {
+        for mut component in item {
+            assert_eq!(component.access(), Access::Write);
+            component.downcast_mut::<U32>().unwrap().0 += 10;
+            count += 1;
+        }
+    }
+    assert_eq!(count, 1);
+
+    let mut modified = world
+        .view_with_mut(Modified::<Read<U32>>::new(before))
+        .into_iter()
+        .map(|u| u.0)
+        .collect::<Vec<_>>();
+    modified.sort();
+    assert_eq!(modified, [11]);
+}
+
+/// Tests that runtime borrow checks apply to query composed at runtime.
+#[test]
+#[should_panic(expected = "Failed to lock")]
+fn dynamic_query_borrow_conflict() {
+    use crate::query::{DynamicQuery, DynamicTerm};
+
+    let mut world = World::new();
+    world.spawn((U32(1),));
+
+    let query = DynamicQuery::new([DynamicTerm::write::<U32>()]);
+
+    let view = world.view::<&U32>();
+    let _iter = view.iter();
+
+    let mut dynamic = world.view_with(query);
+    dynamic.iter_mut().count();
+}
+
+/// Tests that writes leaving compare-on-write components unchanged are not reported as modifications.
+#[test]
+fn compare_on_write() {
+    use core::any::TypeId;
+
+    use crate::{
+        component::ValueCompare,
+        query::{
+            Alt, BorrowAll, BorrowAny, BorrowOne, DynamicQuery, DynamicTerm, EpochOf, Read, Write,
+        },
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Cmp(u32);
+    impl Component for Cmp {
+        fn value_compare() -> Option<ValueCompare> {
+            Some(ValueCompare::of::<Self>())
+        }
+    }
+
+    let mut builder = World::builder();
+    builder.register_component::<U32>().compare_on_write();
+    let mut world = builder.build();
+
+    let e = world.spawn((Cmp(1), U32(1))).id();
+    let after = world.epoch();
+
+    let modified = |world: &World| {
+        (
+            world
+                .view_with(Modified::<Read<Cmp>>::new(after))
+                .iter()
+                .count(),
+            world
+                .view_with(Modified::<Read<U32>>::new(after))
+                .iter()
+                .count(),
+        )
+    };
+
+    // Unchanged writes through view and `Alt`.
+    for (cmp, mut u) in world.view_mut::<(&mut Cmp, Alt<U32>)>().iter_mut() {
+        cmp.0 = 1;
+        u.0 = 1;
+    }
+    assert_eq!(modified(&world), (0, 0));
+
+    // Value changed and restored within single borrow counts as unchanged.
+    let cmp = world.get::<&mut Cmp>(e).unwrap();
+    cmp.0 = 2;
+    cmp.0 = 1;
+    assert_eq!(modified(&world), (0, 0));
+
+    // Snapshots pending when archetype grows are resolved.
+    let spawned = world
+        .spawn_batch((0..100).map(|_| (Cmp(1), U32(1))))
+        .map(|e| e.id())
+        .collect::<Vec<_>>();
+    assert!(world
+        .view_with(Modified::<Read<Cmp>>::new(after))
+        .get(world.lookup(e).unwrap())
+        .is_none());
+    for id in spawned {
+        world.despawn(id).unwrap();
+    }
+
+    // Unchanged writes through `Modified<&mut T>` resolve previous snapshots first.
+    assert_eq!(
+        world
+            .view_with_mut(Modified::<Write<U32>>::new(after))
+            .iter_mut()
+            .count(),
+        0
+    );
+
+    // Changed writes are reported.
+    world.get::<&mut Cmp>(e).unwrap().0 = 2;
+    world.get::<Alt<U32>>(e).unwrap().0 = 2;
+    assert_eq!(modified(&world), (1, 1));
+
+    // Structural changes keep the result.
+    world.insert(e, Bool(true)).unwrap();
+    assert_eq!(modified(&world), (1, 1));
+
+    // Later unchanged write does not hide earlier change.
+    world.get::<&mut U32>(e).unwrap().0 = 2;
+    assert_eq!(modified(&world), (1, 1));
+
+    let after = world.epoch();
+    world.get::<&mut U32>(e).unwrap().0 = 2;
+    assert_eq!(
+        world
+            .view_with(Modified::<Read<U32>>::new(after))
+            .iter()
+            .count(),
+        0
+    );
+
+    // Borrow and dynamic queries take snapshots too.
+    // Replacing a component resolves pending snapshots first.
+    world.insert(e, Bool(false)).unwrap();
+    let after = world.epoch();
+    let modified = |world: &World| {
+        world
+            .view_with(Modified::<Read<U32>>::new(after))
+            .iter()
+            .count()
+    };
+
+    for u in world.view_mut::<BorrowAny<&mut U32>>().iter_mut() {
+        u.0 = 2;
+    }
+    assert_eq!(modified(&world), 0);
+
+    for u in world.view_mut::<BorrowAll<&mut U32>>().iter_mut() {
+        u.for_each(|u| u.0 = 2);
+    }
+    assert_eq!(modified(&world), 0);
+
+    for u in world
+        .view_with_mut(BorrowOne::<Write<U32>>::new(TypeId::of::<U32>()))
+        .iter_mut()
+    {
+        u.0 = 2;
+    }
+    assert_eq!(modified(&world), 0);
+
+    let dynamic = DynamicQuery::new([DynamicTerm::write::<U32>()]);
+    for item in world.view_with_mut(dynamic).iter_mut() {
+        for mut u in item {
+            u.downcast_mut::<U32>().unwrap().0 = 2;
+        }
+    }
+    assert_eq!(modified(&world), 0);
+
+    // `EpochOf` and `Modified<With<T>>` ignore unchanged writes too.
+    let epoch = world.view::<EpochOf<U32>>().get(world.lookup(e).unwrap());
+    let chunk_epoch = |world: &World| {
+        let archetype = world
+            .archetypes()
+            .iter()
+            .find(|archetype| archetype.entities().contains(&e))
+            .unwrap();
+        let component = archetype.component(TypeId::of::<U32>()).unwrap();
+        unsafe { component.data().chunk_epochs[0] }
+    };
+
+    world.get::<&mut U32>(e).unwrap().0 = 2;
+    assert_eq!(
+        world.view::<EpochOf<U32>>().get(world.lookup(e).unwrap()),
+        epoch
+    );
+    assert_eq!(
+        world
+            .view_with(Modified::<With<U32>>::new(after))
+            .iter()
+            .count(),
+        0
+    );
+
+    // Resolving snapshots restores chunk epochs as well.
+    world.insert(e, Bool(true)).unwrap();
+    assert!(!chunk_epoch(&world).after(after));
+}
+
+/// Tests fetching query items from multiple entities at once.
+#[test]
+fn view_get_many_mut() {
+    use crate::{query::Read, GetManyError};
+
+    let mut world = World::new();
+    let a = world.spawn((U32(1),)).id();
+    let b = world.spawn((U32(2), Str("b"))).id();
+    let c = world.spawn((U32(3),)).id();
+    let d = world.spawn((Str("d"),)).id();
+
+    {
+        let mut view = world.view_mut::<&mut U32>();
+
+        // Entities in the same and different archetypes.
+        let [ua, ub, uc] = view.get_many_mut([a, b, c]).unwrap();
+        core::mem::swap(&mut ua.0, &mut uc.0);
+        ub.0 += 10;
+
+        assert_eq!(
+            view.get_many_mut([a, a]).err(),
+            Some(GetManyError::Duplicate)
+        );
+        assert_eq!(
+            view.get_many_mut([a, d]).err(),
+            Some(GetManyError::Mismatch)
+        );
+    }
+
+    assert_eq!(world.get::<&U32>(a).unwrap().0, 3);
+    assert_eq!(world.get::<&U32>(b).unwrap().0, 12);
+    assert_eq!(world.get::<&U32>(c).unwrap().0, 1);
+
+    // Only fetched entities are marked as modified.
+    let after = world.epoch();
+    world.view_mut::<&mut U32>().get_many_mut([c]).unwrap();
+    let modified = world
+        .view_with(Modified::<Read<U32>>::new(after))
+        .into_iter()
+        .map(|u| u.0)
+        .collect::<Vec<_>>();
+    assert_eq!(modified, [1]);
+
+    world.despawn(c).unwrap();
+    assert_eq!(
+        world.view_mut::<&mut U32>().get_many_mut([a, c]).err(),
+        Some(GetManyError::NoSuchEntity)
+    );
+}
@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+use core::{ops::Range, slice};
+
+use crate::{archetype::Archetype, query::Query, world::World};
+
+/// Cache of archetypes that match query and filter.
+///
+/// Valid until archetype set of the world changes.
+/// Relies on [`QueryArg`] requirement for [`Query::visit_archetype`]
+/// to depend only on the archetype.
+///
+/// [`QueryArg`]: crate::system::QueryArg
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ArchetypeCache {
+    archetype_set_id: Option<u64>,
+    indices: Vec<u32>,
+}
+
+impl ArchetypeCache {
+    /// Returns indices of archetypes matching query and filter.
+    /// Rebuilds the cache if archetype set has changed since last call.
+    #[inline(always)]
+    pub fn update<Q, F>(&mut self, world: &World, query: Q, filter: F) -> &[u32]
+    where
+        Q: Query,
+        F: Query,
+    {
+        let id = world.archetype_set_id();
+        if self.archetype_set_id != Some(id) {
+            self.rebuild(world.archetypes(), query, filter);
+            self.archetype_set_id = Some(id);
+        }
+        &self.indices
+    }
+
+    #[inline(never)]
+    fn rebuild<Q, F>(&mut self, archetypes: &[Archetype], query: Q, filter: F)
+    where
+        Q: Query,
+        F: Query,
+    {
+        self.indices.clear();
+        self.indices.extend(
+            archetypes
+                .iter()
+                .enumerate()
+                .filter(|(_, archetype)| {
+                    filter.visit_archetype(archetype) && query.visit_archetype(archetype)
+                })
+                .map(|(idx, _)| idx as u32),
+        );
+    }
+}
+
+/// Archetypes to be visited by a view.
+#[derive(Clone)]
+pub(crate) enum VisitArchetypes<'a> {
+    /// Visit all archetypes and check each one.
+    All(Range<u32>),
+
+    /// Visit only archetypes matched by the cache.
+    Matched(slice::Iter<'a, u32>),
+}
+
+impl<'a> VisitArchetypes<'a> {
+    #[inline(always)]
+    pub fn new(archetypes: &[Archetype], matches: Option<&'a [u32]>) -> Self {
+        match matches {
+            None => VisitArchetypes::All(0..archetypes.len() as u32),
+            Some(matches) => VisitArchetypes::Matched(matches.iter()),
+        }
+    }
+
+    /// Returns next non-empty archetype that should be visited with query and filter.
+    #[inline(always)]
+    pub fn next_match<'b, Q, F>(
+        &mut self,
+        archetypes: &'b [Archetype],
+        query: Q,
+        filter: F,
+    ) -> Option<(u32, &'b Archetype)>
+    where
+        Q: Query,
+        F: Query,
+    {
+        loop {
+            let arch_idx = match self {
+                VisitArchetypes::All(indices) => indices.next()?,
+                VisitArchetypes::Matched(indices) => *indices.next()?,
+            };
+            let archetype = &archetypes[arch_idx as usize];
+
+            if archetype.is_empty() {
+                continue;
+            }
+
+            // Cached matches are checked again
+            // since queries are not required to be consistent.
+            if !filter.visit_archetype(archetype) || !query.visit_archetype(archetype) {
+                continue;
+            }
+
+            if !unsafe { filter.visit_archetype_late(archetype) }
+                || !unsafe { query.visit_archetype_late(archetype) }
+            {
+                continue;
+            }
+
+            return Some((arch_idx, archetype));
+        }
+    }
+}
@@ -80,6 +80,7 @@ where
             query,
             filter,
             archetypes,
+            matches: None,
             entity_set,
             epochs,
             state,
@@ -114,6 +115,7 @@ where
             query,
             filter,
             archetypes,
+            matches: None,
             entity_set,
             epochs,
             state,
@@ -364,6 +366,7 @@ where
             query,
             filter,
             archetypes,
+            matches: None,
             entity_set,
             epochs,
             state,
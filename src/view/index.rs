@@ -3,8 +3,8 @@ use core::ops::Index;
 use crate::{
     entity::{AliveEntity, Entity, EntityId, Location},
     query::{ImmutableQuery, Query, QueryItem, Read},
-    view::get_at,
-    EntityError, NoSuchEntity,
+    view::{get_at, get_many_at},
+    EntityError, GetManyError, NoSuchEntity,
 };
 
 use super::{expect_alive, expect_match, BorrowState, ViewValue};
@@ -121,6 +121,56 @@ where
             .ok_or(EntityError::Mismatch)
     }
 
+    /// Fetches data that matches the view's query and filter
+    /// from multiple distinct entities at once.
+    ///
+    /// Returns `Err(GetManyError::Duplicate)` if same entity is specified more than once.
+    /// Returns `Err(GetManyError::NoSuchEntity)` if any entity is not alive.
+    /// Returns `Err(GetManyError::Mismatch)` if any entity does not match the view's query and filter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component};
+    /// #[derive(Component)]
+    /// struct Velocity(f32);
+    ///
+    /// let mut world = World::new();
+    /// let a = world.spawn((Velocity(1.0),)).id();
+    /// let b = world.spawn((Velocity(-1.0),)).id();
+    ///
+    /// let mut view = world.view_mut::<&mut Velocity>();
+    /// let [va, vb] = view.get_many_mut([a, b]).unwrap();
+    /// core::mem::swap(&mut va.0, &mut vb.0);
+    ///
+    /// assert_eq!(view.try_get_mut(a).unwrap().0, -1.0);
+    /// assert!(view.get_many_mut([a, a]).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        entities: [impl Entity; N],
+    ) -> Result<[QueryItem<Q>; N], GetManyError> {
+        let mut found = [(EntityId::dangling(), Location::reserved(0)); N];
+
+        for (i, entity) in entities.iter().enumerate() {
+            let id = entity.id();
+            if found[..i].iter().any(|(other, _)| *other == id) {
+                return Err(GetManyError::Duplicate);
+            }
+            let loc = entity
+                .lookup(self.entity_set)
+                .ok_or(GetManyError::NoSuchEntity)?;
+            found[i] = (id, loc);
+        }
+
+        // Ensure to borrow view's data.
+        self.acquire_borrow();
+
+        unsafe { get_many_at(self.query, self.filter, self.epochs, self.archetypes, found) }
+            .ok_or(GetManyError::Mismatch)
+    }
+
     #[inline(always)]
     fn _get_reserved(&self, id: EntityId, loc: Location) -> Option<QueryItem<Q>> {
         debug_assert_eq!(loc.arch, u32::MAX);
@@ -6,7 +6,9 @@ use crate::{
     query::{AsQuery, BatchFetch, BatchQuery, Fetch, ImmutableQuery, Query, QueryBatch, QueryItem},
 };
 
-use super::{BorrowState, RuntimeBorrowState, StaticallyBorrowed, ViewValue};
+use super::{
+    cache::VisitArchetypes, BorrowState, RuntimeBorrowState, StaticallyBorrowed, ViewValue,
+};
 
 /// Iterator over entities with a query `Q` and filter `F`.
 /// Yields query items for every entity matching both the query and the filter.
@@ -60,6 +62,7 @@ where
                 self.query,
                 self.filter,
                 self.archetypes,
+                self.matches,
                 StaticallyBorrowed,
             )
         }
@@ -92,6 +95,7 @@ where
                 self.query,
                 self.filter,
                 self.archetypes,
+                self.matches,
                 StaticallyBorrowed,
             )
         }
@@ -114,11 +118,12 @@ where
         let query = self.query;
         let filter = self.filter;
         let archetypes = self.archetypes;
+        let matches = self.matches;
         let (state, _) = self.extract();
 
         // Safety: Existence of this ViewValue guarantees that the borrow state is valid.
         // Borrow state is given to the iter where it will be released on drop.
-        unsafe { ViewValueIter::new(epoch, query, filter, archetypes, state) }
+        unsafe { ViewValueIter::new(epoch, query, filter, archetypes, matches, state) }
     }
 }
 
@@ -131,7 +136,7 @@ pub struct ViewValueIter<'a, Q: Query, F: Query, B: BorrowState> {
     filter_fetch: F::Fetch<'a>,
     epoch: EpochId,
     archetypes: &'a [Archetype],
-    next_archetype: usize,
+    visit: VisitArchetypes<'a>,
     indices: Range<u32>,
     touch_chunk: bool,
     state: B,
@@ -166,6 +171,7 @@ where
         query: Q,
         filter: F,
         archetypes: &'a [Archetype],
+        matches: Option<&'a [u32]>,
         state: B,
     ) -> Self {
         state.acquire(query, filter, archetypes);
@@ -176,7 +182,7 @@ where
             query_fetch: Fetch::dangling(),
             filter_fetch: Fetch::dangling(),
             epoch,
-            next_archetype: 0,
+            visit: VisitArchetypes::new(archetypes, matches),
             indices: 0..0,
             touch_chunk: false,
             state,
@@ -195,22 +201,12 @@ where
 
     #[inline(always)]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let upper = self.archetypes[self.next_archetype..].iter().fold(
-            self.indices.len(),
-            |acc, archetype| {
-                if !self.filter.visit_archetype(archetype) || !self.query.visit_archetype(archetype)
-                {
-                    return acc;
-                }
-
-                if !unsafe { self.filter.visit_archetype_late(archetype) }
-                    || !unsafe { self.query.visit_archetype_late(archetype) }
-                {
-                    return acc;
-                }
-                acc + archetype.len()
-            },
-        );
+        let mut visit = self.visit.clone();
+        let mut upper = self.indices.len();
+        while let Some((_, archetype)) = visit.next_match(self.archetypes, self.query, self.filter)
+        {
+            upper += archetype.len();
+        }
 
         (0, Some(upper))
     }
@@ -221,37 +217,14 @@ where
             match self.indices.next() {
                 None => {
                     // move to the next archetype.
-                    loop {
-                        if self.next_archetype >= self.archetypes.len() {
-                            return None;
-                        }
-                        let arch_idx = self.next_archetype;
-                        self.next_archetype += 1;
-                        let archetype = &self.archetypes[arch_idx];
-
-                        if archetype.is_empty() {
-                            continue;
-                        }
-
-                        if !self.filter.visit_archetype(archetype)
-                            || !self.query.visit_archetype(archetype)
-                        {
-                            continue;
-                        }
-
-                        if !unsafe { self.filter.visit_archetype_late(archetype) }
-                            || !unsafe { self.query.visit_archetype_late(archetype) }
-                        {
-                            continue;
-                        }
-
-                        self.filter_fetch =
-                            unsafe { self.filter.fetch(arch_idx as u32, archetype, self.epoch) };
-                        self.query_fetch =
-                            unsafe { self.query.fetch(arch_idx as u32, archetype, self.epoch) };
-                        self.indices = 0..archetype.len() as u32;
-                        break;
-                    }
+                    let (arch_idx, archetype) =
+                        self.visit
+                            .next_match(self.archetypes, self.query, self.filter)?;
+
+                    self.filter_fetch =
+                        unsafe { self.filter.fetch(arch_idx, archetype, self.epoch) };
+                    self.query_fetch = unsafe { self.query.fetch(arch_idx, archetype, self.epoch) };
+                    self.indices = 0..archetype.len() as u32;
                 }
                 Some(entity_idx) => {
                     if let Some(chunk_idx) = first_of_chunk(entity_idx) {
@@ -324,26 +297,12 @@ where
             acc = f(acc, item);
         }
 
-        for arch_idx in self.next_archetype..self.archetypes.len() {
-            let archetype = &self.archetypes[arch_idx];
-            if archetype.is_empty() {
-                continue;
-            }
-
-            if !self.filter.visit_archetype(archetype) || !self.query.visit_archetype(archetype) {
-                continue;
-            }
-
-            if !unsafe { self.filter.visit_archetype_late(archetype) }
-                || !unsafe { self.query.visit_archetype_late(archetype) }
-            {
-                continue;
-            }
-
-            let mut filter_fetch =
-                unsafe { self.filter.fetch(arch_idx as u32, archetype, self.epoch) };
-            let mut query_fetch =
-                unsafe { self.query.fetch(arch_idx as u32, archetype, self.epoch) };
+        while let Some((arch_idx, archetype)) =
+            self.visit
+                .next_match(self.archetypes, self.query, self.filter)
+        {
+            let mut filter_fetch = unsafe { self.filter.fetch(arch_idx, archetype, self.epoch) };
+            let mut query_fetch = unsafe { self.query.fetch(arch_idx, archetype, self.epoch) };
 
             let mut indices = 0..archetype.len() as u32;
 
@@ -405,6 +364,7 @@ where
                 self.query,
                 self.filter,
                 self.archetypes,
+                self.matches,
                 StaticallyBorrowed,
                 batch_size,
             )
@@ -438,6 +398,7 @@ where
                 self.query,
                 self.filter,
                 self.archetypes,
+                self.matches,
                 StaticallyBorrowed,
                 batch_size,
             )
@@ -459,11 +420,14 @@ where
         let query = self.query;
         let filter = self.filter;
         let archetypes = self.archetypes;
+        let matches = self.matches;
         let (state, _) = self.extract();
 
         // Safety: Existence of this ViewValue guarantees that the borrow state is valid.
         // Borrow state is given to the iter where it will be released on drop.
-        unsafe { ViewValueBatchIter::new(epoch, query, filter, archetypes, state, batch_size) }
+        unsafe {
+            ViewValueBatchIter::new(epoch, query, filter, archetypes, matches, state, batch_size)
+        }
     }
 }
 
@@ -476,7 +440,7 @@ pub struct ViewValueBatchIter<'a, Q: BatchQuery, F: BatchQuery, B: BorrowState>
     filter_fetch: F::Fetch<'a>,
     epoch: EpochId,
     archetypes: &'a [Archetype],
-    next_archetype: usize,
+    visit: VisitArchetypes<'a>,
     indices: Range<u32>,
     state: B,
     batch_size: u32,
@@ -511,6 +475,7 @@ where
         query: Q,
         filter: F,
         archetypes: &'a [Archetype],
+        matches: Option<&'a [u32]>,
         state: B,
         batch_size: u32,
     ) -> Self {
@@ -523,7 +488,7 @@ where
             filter_fetch: Fetch::dangling(),
             epoch,
             archetypes,
-            next_archetype: 0,
+            visit: VisitArchetypes::new(archetypes, matches),
             indices: 0..0,
             state,
             batch_size,
@@ -541,22 +506,12 @@ where
 
     #[inline(always)]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let upper = self.archetypes[self.next_archetype..].iter().fold(
-            self.indices.len(),
-            |acc, archetype| {
-                if !self.filter.visit_archetype(archetype) || !self.query.visit_archetype(archetype)
-                {
-                    return acc;
-                }
-
-                if !unsafe { self.filter.visit_archetype_late(archetype) }
-                    || !unsafe { self.query.visit_archetype_late(archetype) }
-                {
-                    return acc;
-                }
-                acc + archetype.len()
-            },
-        );
+        let mut visit = self.visit.clone();
+        let mut upper = self.indices.len();
+        while let Some((_, archetype)) = visit.next_match(self.archetypes, self.query, self.filter)
+        {
+            upper += archetype.len();
+        }
 
         (0, Some(upper))
     }
@@ -567,37 +522,14 @@ where
             match self.indices.is_empty() {
                 true => {
                     // move to the next archetype.
-                    loop {
-                        if self.next_archetype >= self.archetypes.len() {
-                            return None;
-                        }
-                        let arch_idx = self.next_archetype;
-                        self.next_archetype += 1;
-                        let archetype = &self.archetypes[arch_idx];
-
-                        if archetype.is_empty() {
-                            continue;
-                        }
-
-                        if !self.filter.visit_archetype(archetype)
-                            || !self.query.visit_archetype(archetype)
-                        {
-                            continue;
-                        }
-
-                        if !unsafe { self.filter.visit_archetype_late(archetype) }
-                            || !unsafe { self.query.visit_archetype_late(archetype) }
-                        {
-                            continue;
-                        }
-
-                        self.filter_fetch =
-                            unsafe { self.filter.fetch(arch_idx as u32, archetype, self.epoch) };
-                        self.query_fetch =
-                            unsafe { self.query.fetch(arch_idx as u32, archetype, self.epoch) };
-                        self.indices = 0..archetype.len() as u32;
-                        break;
-                    }
+                    let (arch_idx, archetype) =
+                        self.visit
+                            .next_match(self.archetypes, self.query, self.filter)?;
+
+                    self.filter_fetch =
+                        unsafe { self.filter.fetch(arch_idx, archetype, self.epoch) };
+                    self.query_fetch = unsafe { self.query.fetch(arch_idx, archetype, self.epoch) };
+                    self.indices = 0..archetype.len() as u32;
                 }
                 false => {
                     let start = self.indices.start;
@@ -641,26 +573,12 @@ where
             acc = f(acc, batch);
         }
 
-        for arch_idx in self.next_archetype..self.archetypes.len() {
-            let archetype = &self.archetypes[arch_idx];
-            if archetype.is_empty() {
-                continue;
-            }
-
-            if !self.filter.visit_archetype(archetype) || !self.query.visit_archetype(archetype) {
-                continue;
-            }
-
-            if !unsafe { self.filter.visit_archetype_late(archetype) }
-                || !unsafe { self.query.visit_archetype_late(archetype) }
-            {
-                continue;
-            }
-
-            let mut filter_fetch =
-                unsafe { self.filter.fetch(arch_idx as u32, archetype, self.epoch) };
-            let mut query_fetch =
-                unsafe { self.query.fetch(arch_idx as u32, archetype, self.epoch) };
+        while let Some((arch_idx, archetype)) =
+            self.visit
+                .next_match(self.archetypes, self.query, self.filter)
+        {
+            let mut filter_fetch = unsafe { self.filter.fetch(arch_idx, archetype, self.epoch) };
+            let mut query_fetch = unsafe { self.query.fetch(arch_idx, archetype, self.epoch) };
 
             let mut indices = 0..archetype.len() as u32;
 
@@ -9,7 +9,7 @@
 use crate::{
     archetype::{chunk_idx, Archetype},
     component::ComponentInfo,
-    entity::{EntitySet, Location},
+    entity::{EntityId, EntitySet, Location},
     epoch::EpochCounter,
     query::{AsQuery, Fetch, Query, QueryItem},
     world::World,
@@ -24,11 +24,15 @@ pub use self::{
     one::{ViewOne, ViewOneValue},
 };
 
+pub(crate) use self::cache::ArchetypeCache;
+
 mod borrow;
+mod cache;
 mod extend;
 mod index;
 mod iter;
 mod one;
+mod par;
 
 /// Flag indicating that view is extensible.
 #[derive(Copy, Clone)]
@@ -50,6 +54,7 @@ impl From<Extensible> for NonExtensible {
 #[must_use]
 pub struct ViewValue<'a, Q: Query, F: Query, B: BorrowState, E> {
     archetypes: &'a [Archetype],
+    matches: Option<&'a [u32]>,
     query: Q,
     filter: F,
     state: B,
@@ -128,6 +133,7 @@ where
         self.acquire_borrow();
         ViewValue {
             archetypes: self.archetypes,
+            matches: self.matches,
             query: self.query,
             filter: self.filter,
             state: StaticallyBorrowed,
@@ -218,6 +224,7 @@ where
     ) -> Self {
         ViewValue {
             archetypes: world.archetypes(),
+            matches: None,
             query,
             filter,
             state,
@@ -226,6 +233,18 @@ where
             extensibility,
         }
     }
+
+    /// Restricts archetypes visited by iterators to ones with provided indices.
+    ///
+    /// # Safety
+    ///
+    /// `matches` must contain indices of all archetypes of the world
+    /// that match query and filter and no other archetypes.
+    #[inline(always)]
+    pub(crate) unsafe fn with_matches(mut self, matches: &'a [u32]) -> Self {
+        self.matches = Some(matches);
+        self
+    }
 }
 
 impl<'a, Q, F> ViewValue<'a, Q, F, StaticallyBorrowed, Extensible>
@@ -275,6 +294,7 @@ where
     fn from(view: ViewValue<'a, Q, F, StaticallyBorrowed, E>) -> Self {
         let query = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -282,6 +302,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state: RuntimeBorrowState::new(),
@@ -302,6 +323,7 @@ where
     fn from(view: ViewValue<'a, Q, F, B, Extensible>) -> Self {
         let query = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -309,6 +331,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state,
@@ -329,6 +352,7 @@ where
     fn from(view: ViewValue<'a, Q, F, StaticallyBorrowed, Extensible>) -> Self {
         let query = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -336,6 +360,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state: RuntimeBorrowState::new(),
@@ -356,6 +381,7 @@ where
     fn from(view: ViewValue<'a, (Q,), F, B, E>) -> Self {
         let (query,) = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -363,6 +389,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state,
@@ -383,6 +410,7 @@ where
     fn from(view: ViewValue<'a, (Q,), F, StaticallyBorrowed, E>) -> Self {
         let (query,) = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -390,6 +418,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state: RuntimeBorrowState::new(),
@@ -411,6 +440,7 @@ where
     fn from(view: ViewValue<'a, (Q,), F, B, Extensible>) -> Self {
         let (query,) = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -418,6 +448,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state,
@@ -438,6 +469,7 @@ where
     fn from(view: ViewValue<'a, (Q,), F, StaticallyBorrowed, Extensible>) -> Self {
         let (query,) = view.query;
         let archetypes = view.archetypes;
+        let matches = view.matches;
         let filter = view.filter;
         let entity_set = view.entity_set;
         let epochs = view.epochs;
@@ -445,6 +477,7 @@ where
 
         ViewValue {
             archetypes,
+            matches,
             query,
             filter,
             state: RuntimeBorrowState::new(),
@@ -525,6 +558,111 @@ where
     Some(unsafe { Fetch::get_item(&mut query_fetch, idx) })
 }
 
+/// Query and filter fetches for an archetype.
+type ArchetypeFetch<'a, Q, F> = (u32, <Q as Query>::Fetch<'a>, <F as Query>::Fetch<'a>);
+
+/// Fetches items from multiple distinct entities.
+///
+/// Entities in the same archetype share a single fetch,
+/// so their items may be alive at the same time.
+#[inline(always)]
+unsafe fn get_many_at<'a, Q, F, const N: usize>(
+    query: Q,
+    filter: F,
+    epochs: &EpochCounter,
+    archetypes: &'a [Archetype],
+    entities: [(EntityId, Location); N],
+) -> Option<[QueryItem<'a, Q>; N]>
+where
+    Q: Query,
+    F: Query,
+{
+    let epoch = epochs.next_if(Q::Query::MUTABLE || F::Query::MUTABLE);
+
+    // Fetches for each visited archetype and index of the fetch used by each entity.
+    let mut fetches: [Option<ArchetypeFetch<'a, Q, F>>; N] = core::array::from_fn(|_| None);
+    let mut fetch_idx = [usize::MAX; N];
+    let mut items = [(); N].map(|_| None);
+
+    for i in 0..N {
+        let (id, Location { arch, idx }) = entities[i];
+
+        if arch == u32::MAX {
+            items[i] = Some(Query::reserved_entity_item(&query, id, idx)?);
+            continue;
+        }
+
+        let archetype = &archetypes[arch as usize];
+        assert!(idx < archetype.len() as u32, "Wrong location");
+
+        let shared = (0..i).find(|&j| matches!(&fetches[j], Some((a, _, _)) if *a == arch));
+
+        let (f, touched) = match shared {
+            Some(j) => {
+                // Chunk is already touched if another entity from it is visited.
+                let touched = (0..i)
+                    .any(|k| fetch_idx[k] == j && chunk_idx(entities[k].1.idx) == chunk_idx(idx));
+                (j, touched)
+            }
+            None => {
+                if !query.visit_archetype(archetype) || !filter.visit_archetype(archetype) {
+                    return None;
+                }
+
+                if !unsafe { query.visit_archetype_late(archetype) }
+                    || !unsafe { filter.visit_archetype_late(archetype) }
+                {
+                    return None;
+                }
+
+                let query_fetch = unsafe { query.fetch(arch, archetype, epoch) };
+                let filter_fetch = unsafe { filter.fetch(arch, archetype, epoch) };
+                fetches[i] = Some((arch, query_fetch, filter_fetch));
+                (i, false)
+            }
+        };
+        fetch_idx[i] = f;
+
+        let (_, query_fetch, filter_fetch) = fetches[f].as_mut().unwrap();
+
+        if !touched {
+            if !unsafe { Fetch::visit_chunk(query_fetch, chunk_idx(idx)) } {
+                return None;
+            }
+
+            unsafe { Fetch::touch_chunk(query_fetch, chunk_idx(idx)) }
+        }
+
+        if !unsafe { Fetch::visit_item(query_fetch, idx) } {
+            return None;
+        }
+
+        if !touched {
+            if !unsafe { Fetch::visit_chunk(filter_fetch, chunk_idx(idx)) } {
+                return None;
+            }
+
+            unsafe { Fetch::touch_chunk(filter_fetch, chunk_idx(idx)) }
+        }
+
+        if !unsafe { Fetch::visit_item(filter_fetch, idx) } {
+            return None;
+        }
+    }
+
+    // All entities match. Fetch items.
+    for i in 0..N {
+        let (_, Location { arch, idx }) = entities[i];
+
+        if arch != u32::MAX {
+            let (_, query_fetch, _) = fetches[fetch_idx[i]].as_mut().unwrap();
+            items[i] = Some(unsafe { Fetch::get_item(query_fetch, idx) });
+        }
+    }
+
+    Some(items.map(|item| item.unwrap()))
+}
+
 #[track_caller]
 #[inline(always)]
 fn has_conflict_query_filter<Q, F>(query: Q, filter: F, comp: &ComponentInfo) -> bool
@@ -0,0 +1,360 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    archetype::{chunk_idx, first_of_chunk, Archetype, CHUNK_LEN},
+    epoch::EpochCounter,
+    executor::ScopedExecutor,
+    query::{Fetch, Query, QueryItem, SendQuery},
+};
+
+use super::{cache::VisitArchetypes, BorrowState, ViewValue};
+
+/// Part of a single archetype that can be iterated independently.
+///
+/// Covers whole chunks, so that chunk epochs are never touched
+/// from two batches simultaneously.
+pub(super) struct ParBatch<'a, Q: Query, F: Query> {
+    query_fetch: Q::Fetch<'a>,
+    filter_fetch: F::Fetch<'a>,
+    indices: Range<u32>,
+    touch_chunk: bool,
+}
+
+// Safety: Batches access disjoint set of entities and chunks.
+// Archetype level state is modified only when fetch is created on the calling thread.
+// `SendQuery` guarantees that components may be accessed from other threads.
+unsafe impl<Q, F> Send for ParBatch<'_, Q, F>
+where
+    Q: SendQuery,
+    F: SendQuery,
+{
+}
+
+impl<'a, Q, F> Iterator for ParBatch<'a, Q, F>
+where
+    Q: Query,
+    F: Query,
+{
+    type Item = QueryItem<'a, Q>;
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.indices.len()))
+    }
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<QueryItem<'a, Q>> {
+        while let Some(entity_idx) = self.indices.next() {
+            if let Some(chunk_idx) = first_of_chunk(entity_idx) {
+                if !unsafe { self.filter_fetch.visit_chunk(chunk_idx) } {
+                    self.indices.nth(CHUNK_LEN as usize - 2);
+                    continue;
+                }
+                if !unsafe { self.query_fetch.visit_chunk(chunk_idx) } {
+                    self.indices.nth(CHUNK_LEN as usize - 2);
+                    continue;
+                }
+                self.touch_chunk = true;
+            }
+
+            if !unsafe { self.filter_fetch.visit_item(entity_idx) } {
+                continue;
+            }
+
+            if !unsafe { self.query_fetch.visit_item(entity_idx) } {
+                continue;
+            }
+
+            if self.touch_chunk {
+                unsafe { self.filter_fetch.touch_chunk(chunk_idx(entity_idx)) }
+                unsafe { self.query_fetch.touch_chunk(chunk_idx(entity_idx)) }
+                self.touch_chunk = false;
+            }
+
+            return Some(unsafe { self.query_fetch.get_item(entity_idx) });
+        }
+        None
+    }
+}
+
+/// Splits matching archetypes into batches of whole chunks.
+///
+/// Each batch gets its own fetch, created on the calling thread
+/// with a separate epoch.
+///
+/// # Safety
+///
+/// Borrows for the query and filter must be acquired
+/// and held until all batches are dropped.
+unsafe fn par_batches<'a, Q, F>(
+    query: Q,
+    filter: F,
+    archetypes: &'a [Archetype],
+    matches: Option<&'a [u32]>,
+    epochs: &EpochCounter,
+    tasks: usize,
+) -> Vec<ParBatch<'a, Q, F>>
+where
+    Q: Query,
+    F: Query,
+{
+    let mut visit = VisitArchetypes::new(archetypes, matches);
+
+    let mut total = 0;
+    let mut counting = visit.clone();
+    while let Some((_, archetype)) = counting.next_match(archetypes, query, filter) {
+        total += archetype.len();
+    }
+
+    let batch_len = total.div_ceil(tasks.max(1)) as u32;
+    let batch_len = batch_len.next_multiple_of(CHUNK_LEN).max(CHUNK_LEN);
+
+    let mut batches = Vec::new();
+
+    while let Some((arch_idx, archetype)) = visit.next_match(archetypes, query, filter) {
+        let len = archetype.len() as u32;
+        let mut start = 0;
+        while start < len {
+            let end = len.min(start + batch_len);
+            let epoch = epochs.next_if(Q::MUTABLE || F::MUTABLE);
+
+            // Batches cover disjoint ranges of whole chunks.
+            batches.push(ParBatch {
+                query_fetch: unsafe { query.fetch(arch_idx, archetype, epoch) },
+                filter_fetch: unsafe { filter.fetch(arch_idx, archetype, epoch) },
+                indices: start..end,
+                touch_chunk: false,
+            });
+            start = end;
+        }
+    }
+
+    batches
+}
+
+impl<'a, Q, F, B, E> ViewValue<'a, Q, F, B, E>
+where
+    Q: SendQuery,
+    F: SendQuery,
+    B: BorrowState,
+{
+    /// Calls provided function for each entity with a query `Q` and filter `F`,
+    /// spawning tasks on the provided executor.
+    ///
+    /// Work is split across archetypes and across chunks within archetypes.
+    /// View stays borrowed until the scope of the executor ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component, executor::MockExecutor};
+    /// #[derive(Component)]
+    /// struct Value(u32);
+    ///
+    /// let mut world = World::new();
+    /// for i in 0..1000u32 {
+    ///     world.spawn((Value(i),));
+    /// }
+    ///
+    /// let mut view = world.view_mut::<&mut Value>();
+    /// view.par_for_each_with(&MockExecutor, |value| value.0 *= 2);
+    /// drop(view);
+    ///
+    /// assert_eq!(world.view::<&Value>().into_iter().map(|v| v.0).sum::<u32>(), 999_000);
+    /// ```
+    pub fn par_for_each_with<'scope, X, Fun>(&'scope mut self, executor: &X, f: Fun)
+    where
+        X: ScopedExecutor<'scope>,
+        Fun: Fn(QueryItem<'scope, Q>) + Send + Sync + 'scope,
+    {
+        let tasks = default_tasks();
+
+        self.acquire_borrow();
+
+        // Safety: we just acquired the borrow.
+        // Releasing requires a mutable reference to self which is borrowed for `'scope`.
+        let batches = unsafe {
+            par_batches(
+                self.query,
+                self.filter,
+                self.archetypes,
+                self.matches,
+                self.epochs,
+                tasks,
+            )
+        };
+
+        let total: usize = batches.iter().map(|batch| batch.indices.len()).sum();
+        let task_len = total.div_ceil(tasks.max(1)).max(1);
+
+        let f = Arc::new(f);
+        let mut task = Vec::new();
+        let mut task_size = 0;
+
+        for batch in batches {
+            task_size += batch.indices.len();
+            task.push(batch);
+
+            if task_size >= task_len {
+                spawn_task(executor, core::mem::take(&mut task), f.clone());
+                task_size = 0;
+            }
+        }
+
+        if !task.is_empty() {
+            spawn_task(executor, task, f);
+        }
+    }
+
+    /// Calls provided function for each entity with a query `Q` and filter `F`
+    /// in parallel.
+    ///
+    /// Uses rayon if `rayon` feature is enabled, otherwise scoped threads.
+    /// Returns after all entities are visited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component};
+    /// #[derive(Component)]
+    /// struct Value(u32);
+    ///
+    /// let mut world = World::new();
+    /// for i in 0..1000u32 {
+    ///     world.spawn((Value(i),));
+    /// }
+    ///
+    /// world.view_mut::<&mut Value>().par_for_each(|value| value.0 *= 2);
+    ///
+    /// assert_eq!(world.view::<&Value>().into_iter().map(|v| v.0).sum::<u32>(), 999_000);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn par_for_each<Fun>(&mut self, f: Fun)
+    where
+        Fun: Fn(QueryItem<'_, Q>) + Send + Sync,
+    {
+        #[cfg(feature = "rayon")]
+        rayon::in_place_scope(|scope| self.par_for_each_with(scope, f));
+
+        #[cfg(not(feature = "rayon"))]
+        std::thread::scope(|scope| self.par_for_each_with(&scope, f));
+    }
+
+    /// Returns parallel iterator over entities with a query `Q` and filter `F`.
+    ///
+    /// Work is split across archetypes and across chunks within archetypes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component};
+    /// use rayon::iter::ParallelIterator;
+    ///
+    /// #[derive(Component)]
+    /// struct Value(u32);
+    ///
+    /// let mut world = World::new();
+    /// for i in 0..1000u32 {
+    ///     world.spawn((Value(i),));
+    /// }
+    ///
+    /// let mut view = world.view_mut::<&mut Value>();
+    /// view.par_iter_mut().for_each(|value| value.0 *= 2);
+    /// assert_eq!(view.par_iter_mut().map(|value| value.0).sum::<u32>(), 999_000);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> impl rayon::iter::ParallelIterator<Item = QueryItem<'_, Q>>
+    where
+        for<'b> QueryItem<'b, Q>: Send,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        self.acquire_borrow();
+
+        // Safety: we just acquired the borrow. Releasing requires a mutable reference to self.
+        // This ensures that it can only happen after the iterator is dropped.
+        let batches = unsafe {
+            par_batches(
+                self.query,
+                self.filter,
+                self.archetypes,
+                self.matches,
+                self.epochs,
+                rayon::current_num_threads() * 4,
+            )
+        };
+
+        batches.into_par_iter().flat_map_iter(|batch| batch)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, Q, F, B, E> ViewValue<'a, Q, F, B, E>
+where
+    Q: SendQuery + crate::query::ImmutableQuery,
+    F: SendQuery + crate::query::ImmutableQuery,
+    B: BorrowState,
+{
+    /// Returns parallel iterator over entities with a query `Q` and filter `F`.
+    ///
+    /// Unlike `par_iter_mut`, this version only works for views with immutable queries.
+    pub fn par_iter(&self) -> impl rayon::iter::ParallelIterator<Item = QueryItem<'_, Q>>
+    where
+        for<'b> QueryItem<'b, Q>: Send,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        debug_assert!(!Q::MUTABLE && !F::MUTABLE);
+
+        self.acquire_borrow();
+
+        // Safety: we just acquired the borrow. Releasing requires a mutable reference to self.
+        // This ensures that it can only happen after the iterator is dropped.
+        let batches = unsafe {
+            par_batches(
+                self.query,
+                self.filter,
+                self.archetypes,
+                self.matches,
+                self.epochs,
+                rayon::current_num_threads() * 4,
+            )
+        };
+
+        batches.into_par_iter().flat_map_iter(|batch| batch)
+    }
+}
+
+fn spawn_task<'scope, X, Q, F, Fun>(executor: &X, task: Vec<ParBatch<'scope, Q, F>>, f: Arc<Fun>)
+where
+    X: ScopedExecutor<'scope>,
+    Q: SendQuery,
+    F: SendQuery,
+    Fun: Fn(QueryItem<'scope, Q>) + Send + Sync + 'scope,
+{
+    executor.spawn(move |_| {
+        for batch in task {
+            batch.for_each(&*f);
+        }
+    });
+}
+
+/// Number of tasks to split parallel iteration into.
+#[inline(always)]
+fn default_tasks() -> usize {
+    #[cfg(feature = "rayon")]
+    {
+        rayon::current_num_threads()
+    }
+
+    #[cfg(all(feature = "std", not(feature = "rayon")))]
+    {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        1
+    }
+}
@@ -24,6 +24,7 @@ use super::{
 pub struct WorldBuilder {
     registry: ComponentRegistry,
     range_alloc: Option<Box<dyn IdRangeAllocator>>,
+    action_channel_capacity: Option<usize>,
 }
 
 impl WorldBuilder {
@@ -33,6 +34,7 @@ impl WorldBuilder {
         WorldBuilder {
             registry: ComponentRegistry::new(),
             range_alloc: None,
+            action_channel_capacity: None,
         }
     }
 
@@ -52,7 +54,7 @@ impl WorldBuilder {
             resources: Resources::new(),
             registry: self.registry,
             action_buffer: UnsafeCell::new(LocalActionBuffer::new()),
-            action_channel: ActionChannel::new(),
+            action_channel: ActionChannel::new(self.action_channel_capacity),
 
             #[cfg(feature = "flow")]
             new_flows: UnsafeCell::new(crate::flow::NewFlows::new()),
@@ -93,6 +95,19 @@ impl WorldBuilder {
         self.range_alloc = Some(range_alloc);
         self
     }
+
+    /// Sets maximum number of actions that can be pending in the channel
+    /// behind [`World::new_action_sender`].
+    /// If capacity is not set, the channel is unbounded.
+    ///
+    /// When channel is full, [`ActionSender`] drops new actions
+    /// unless `try_*` or `*_blocking` methods are used.
+    ///
+    /// [`ActionSender`]: crate::action::ActionSender
+    pub fn with_action_channel_capacity(mut self, capacity: usize) -> Self {
+        self.action_channel_capacity = Some(capacity);
+        self
+    }
 }
 
 impl World {
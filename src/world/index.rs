@@ -0,0 +1,80 @@
+use core::any::type_name;
+
+use crate::index::{Index, IndexRef, IndexState};
+
+use super::World;
+
+impl World {
+    /// Registers index `I` in the world.
+    /// Does nothing if index is already registered.
+    ///
+    /// Index is populated lazily on first access with [`World::index`].
+    ///
+    /// See [`edict::index`](crate::index) for details.
+    pub fn register_index<I>(&mut self)
+    where
+        I: Index,
+    {
+        if self.resources.get::<IndexState<I>>().is_none() {
+            self.resources.insert(IndexState::<I>::new());
+        }
+    }
+
+    /// Returns reference to the index `I`.
+    /// Brings index up to date with changes made in the world since last access.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if index is not registered.
+    /// Or if index is already borrowed or indexed component is borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use edict::{world::World, component::Component, index::{Index, OrdIndex}};
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// struct ByScore;
+    ///
+    /// impl Index for ByScore {
+    ///     type Component = Score;
+    ///     type Key = u32;
+    ///     type Storage = OrdIndex<u32>;
+    ///
+    ///     fn key(score: &Score) -> u32 {
+    ///         score.0
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.register_index::<ByScore>();
+    ///
+    /// let a = world.spawn((Score(10),)).id();
+    /// let b = world.spawn((Score(20),)).id();
+    /// let c = world.spawn((Score(30),)).id();
+    ///
+    /// let top = world
+    ///     .index::<ByScore>()
+    ///     .range(15..)
+    ///     .map(|(_, id)| id)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(top, [b, c]);
+    ///
+    /// world.despawn(c).unwrap();
+    /// assert_eq!(world.index::<ByScore>().range(15..).count(), 1);
+    /// # let _ = a;
+    /// ```
+    #[track_caller]
+    pub fn index<I>(&self) -> IndexRef<'_, I>
+    where
+        I: Index,
+    {
+        match self.resources.get_mut::<IndexState<I>>() {
+            None => panic!("Index {} is not registered", type_name::<I>()),
+            Some(mut state) => state.sync(self),
+        }
+
+        IndexRef::new(self.resources.get::<IndexState<I>>().unwrap())
+    }
+}
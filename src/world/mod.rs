@@ -21,11 +21,13 @@ use core::{
     fmt::{self, Debug},
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 use crate::{
-    action::{ActionChannel, ActionSender, LocalActionBuffer, LocalActionEncoder},
+    action::{
+        ActionChannel, ActionChannelStats, ActionSender, LocalActionBuffer, LocalActionEncoder,
+    },
     archetype::Archetype,
     bundle::{BundleDesc, ComponentBundleDesc},
     component::{Component, ComponentInfo, ComponentRegistry},
@@ -44,6 +46,7 @@ pub use self::builder::WorldBuilder;
 mod builder;
 mod edges;
 mod get;
+mod index;
 mod insert;
 mod relation;
 mod remove;
@@ -56,7 +59,7 @@ mod view;
 /// `World` keeps same id until archetype set changes.
 ///
 /// This value starts with 1 because 0 is reserved for empty set.
-static NEXT_ARCHETYPE_SET_ID: AtomicUsize = AtomicUsize::new(1);
+static NEXT_ARCHETYPE_SET_ID: AtomicU64 = AtomicU64::new(1);
 
 struct ArchetypeSet {
     /// Unique archetype set id.
@@ -323,6 +326,34 @@ impl World {
         &self.entities
     }
 
+    /// Returns counters of the channel behind [`ActionSender`]s bound to this [`World`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::world::World;
+    /// let mut world = World::builder().with_action_channel_capacity(1).build();
+    ///
+    /// let action_sender = world.new_action_sender();
+    /// action_sender.spawn(());
+    /// action_sender.spawn(());
+    /// assert!(action_sender.try_spawn(()).is_err());
+    ///
+    /// let stats = world.action_channel_stats();
+    /// assert_eq!(stats.pending, 1);
+    /// assert_eq!(stats.dropped, 1);
+    ///
+    /// world.execute_received_actions();
+    ///
+    /// let stats = world.action_channel_stats();
+    /// assert_eq!(stats.pending, 0);
+    /// assert_eq!(stats.executed, 1);
+    /// ```
+    #[inline(always)]
+    pub fn action_channel_stats(&self) -> ActionChannelStats {
+        self.action_channel.stats()
+    }
+
     /// Runs world maintenance.
     ///
     /// Users do not call this method,
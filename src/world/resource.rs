@@ -355,6 +355,11 @@ impl World {
     pub fn resource_types(&self) -> impl Iterator<Item = TypeId> + '_ {
         self.resources.resource_types()
     }
+
+    /// Returns type name of the resource.
+    pub(crate) fn resource_name(&self, ty: TypeId) -> Option<&'static str> {
+        self.resources.resource_name(ty)
+    }
 }
 
 impl WorldLocal {